@@ -0,0 +1,137 @@
+//! Compares the lookup the derive generates for contiguous discriminants
+//! (a bounds check and a transmute) against the match over per-variant
+//! consts that it used to generate, and against a sparse enum which still
+//! goes through that match.
+
+use ::criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ::num_enum::TryFromPrimitive;
+
+macro_rules! opcodes {
+    ($name:ident: $repr:ident, $match_fn:ident, $($variant:ident = $value:expr),* $(,)?) => {
+        #[derive(Clone, Copy, Debug, TryFromPrimitive)]
+        #[repr($repr)]
+        enum $name {
+            $($variant = $value,)*
+        }
+
+        /// What `#[derive(TryFromPrimitive)]` generated before dense lookups.
+        #[allow(non_upper_case_globals)]
+        fn $match_fn(number: $repr) -> Option<$name> {
+            $(const $variant: $repr = $name::$variant as $repr;)*
+            match number {
+                $($variant => Some($name::$variant),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+opcodes! { Dense: u8, dense_match,
+    Op000 = 0, Op001 = 1, Op002 = 2, Op003 = 3, Op004 = 4, Op005 = 5,
+    Op006 = 6, Op007 = 7, Op008 = 8, Op009 = 9, Op010 = 10, Op011 = 11,
+    Op012 = 12, Op013 = 13, Op014 = 14, Op015 = 15, Op016 = 16, Op017 = 17,
+    Op018 = 18, Op019 = 19, Op020 = 20, Op021 = 21, Op022 = 22, Op023 = 23,
+    Op024 = 24, Op025 = 25, Op026 = 26, Op027 = 27, Op028 = 28, Op029 = 29,
+    Op030 = 30, Op031 = 31, Op032 = 32, Op033 = 33, Op034 = 34, Op035 = 35,
+    Op036 = 36, Op037 = 37, Op038 = 38, Op039 = 39, Op040 = 40, Op041 = 41,
+    Op042 = 42, Op043 = 43, Op044 = 44, Op045 = 45, Op046 = 46, Op047 = 47,
+    Op048 = 48, Op049 = 49, Op050 = 50, Op051 = 51, Op052 = 52, Op053 = 53,
+    Op054 = 54, Op055 = 55, Op056 = 56, Op057 = 57, Op058 = 58, Op059 = 59,
+    Op060 = 60, Op061 = 61, Op062 = 62, Op063 = 63, Op064 = 64, Op065 = 65,
+    Op066 = 66, Op067 = 67, Op068 = 68, Op069 = 69, Op070 = 70, Op071 = 71,
+    Op072 = 72, Op073 = 73, Op074 = 74, Op075 = 75, Op076 = 76, Op077 = 77,
+    Op078 = 78, Op079 = 79, Op080 = 80, Op081 = 81, Op082 = 82, Op083 = 83,
+    Op084 = 84, Op085 = 85, Op086 = 86, Op087 = 87, Op088 = 88, Op089 = 89,
+    Op090 = 90, Op091 = 91, Op092 = 92, Op093 = 93, Op094 = 94, Op095 = 95,
+    Op096 = 96, Op097 = 97, Op098 = 98, Op099 = 99, Op100 = 100, Op101 = 101,
+    Op102 = 102, Op103 = 103, Op104 = 104, Op105 = 105, Op106 = 106, Op107 = 107,
+    Op108 = 108, Op109 = 109, Op110 = 110, Op111 = 111, Op112 = 112, Op113 = 113,
+    Op114 = 114, Op115 = 115, Op116 = 116, Op117 = 117, Op118 = 118, Op119 = 119,
+    Op120 = 120, Op121 = 121, Op122 = 122, Op123 = 123, Op124 = 124, Op125 = 125,
+    Op126 = 126, Op127 = 127, Op128 = 128, Op129 = 129, Op130 = 130, Op131 = 131,
+    Op132 = 132, Op133 = 133, Op134 = 134, Op135 = 135, Op136 = 136, Op137 = 137,
+    Op138 = 138, Op139 = 139, Op140 = 140, Op141 = 141, Op142 = 142, Op143 = 143,
+    Op144 = 144, Op145 = 145, Op146 = 146, Op147 = 147, Op148 = 148, Op149 = 149,
+    Op150 = 150, Op151 = 151, Op152 = 152, Op153 = 153, Op154 = 154, Op155 = 155,
+    Op156 = 156, Op157 = 157, Op158 = 158, Op159 = 159, Op160 = 160, Op161 = 161,
+    Op162 = 162, Op163 = 163, Op164 = 164, Op165 = 165, Op166 = 166, Op167 = 167,
+    Op168 = 168, Op169 = 169, Op170 = 170, Op171 = 171, Op172 = 172, Op173 = 173,
+    Op174 = 174, Op175 = 175, Op176 = 176, Op177 = 177, Op178 = 178, Op179 = 179,
+    Op180 = 180, Op181 = 181, Op182 = 182, Op183 = 183, Op184 = 184, Op185 = 185,
+    Op186 = 186, Op187 = 187, Op188 = 188, Op189 = 189, Op190 = 190, Op191 = 191,
+    Op192 = 192, Op193 = 193, Op194 = 194, Op195 = 195, Op196 = 196, Op197 = 197,
+    Op198 = 198, Op199 = 199,
+}
+
+opcodes! { Sparse: u16, sparse_match,
+    Op000 = 0, Op001 = 3, Op002 = 6, Op003 = 9, Op004 = 12, Op005 = 15,
+    Op006 = 18, Op007 = 21, Op008 = 24, Op009 = 27, Op010 = 30, Op011 = 33,
+    Op012 = 36, Op013 = 39, Op014 = 42, Op015 = 45, Op016 = 48, Op017 = 51,
+    Op018 = 54, Op019 = 57, Op020 = 60, Op021 = 63, Op022 = 66, Op023 = 69,
+    Op024 = 72, Op025 = 75, Op026 = 78, Op027 = 81, Op028 = 84, Op029 = 87,
+    Op030 = 90, Op031 = 93, Op032 = 96, Op033 = 99, Op034 = 102, Op035 = 105,
+    Op036 = 108, Op037 = 111, Op038 = 114, Op039 = 117, Op040 = 120, Op041 = 123,
+    Op042 = 126, Op043 = 129, Op044 = 132, Op045 = 135, Op046 = 138, Op047 = 141,
+    Op048 = 144, Op049 = 147, Op050 = 150, Op051 = 153, Op052 = 156, Op053 = 159,
+    Op054 = 162, Op055 = 165, Op056 = 168, Op057 = 171, Op058 = 174, Op059 = 177,
+    Op060 = 180, Op061 = 183, Op062 = 186, Op063 = 189, Op064 = 192, Op065 = 195,
+    Op066 = 198, Op067 = 201, Op068 = 204, Op069 = 207, Op070 = 210, Op071 = 213,
+    Op072 = 216, Op073 = 219, Op074 = 222, Op075 = 225, Op076 = 228, Op077 = 231,
+    Op078 = 234, Op079 = 237, Op080 = 240, Op081 = 243, Op082 = 246, Op083 = 249,
+    Op084 = 252, Op085 = 255, Op086 = 258, Op087 = 261, Op088 = 264, Op089 = 267,
+    Op090 = 270, Op091 = 273, Op092 = 276, Op093 = 279, Op094 = 282, Op095 = 285,
+    Op096 = 288, Op097 = 291, Op098 = 294, Op099 = 297, Op100 = 300, Op101 = 303,
+    Op102 = 306, Op103 = 309, Op104 = 312, Op105 = 315, Op106 = 318, Op107 = 321,
+    Op108 = 324, Op109 = 327, Op110 = 330, Op111 = 333, Op112 = 336, Op113 = 339,
+    Op114 = 342, Op115 = 345, Op116 = 348, Op117 = 351, Op118 = 354, Op119 = 357,
+    Op120 = 360, Op121 = 363, Op122 = 366, Op123 = 369, Op124 = 372, Op125 = 375,
+    Op126 = 378, Op127 = 381, Op128 = 384, Op129 = 387, Op130 = 390, Op131 = 393,
+    Op132 = 396, Op133 = 399, Op134 = 402, Op135 = 405, Op136 = 408, Op137 = 411,
+    Op138 = 414, Op139 = 417, Op140 = 420, Op141 = 423, Op142 = 426, Op143 = 429,
+    Op144 = 432, Op145 = 435, Op146 = 438, Op147 = 441, Op148 = 444, Op149 = 447,
+    Op150 = 450, Op151 = 453, Op152 = 456, Op153 = 459, Op154 = 462, Op155 = 465,
+    Op156 = 468, Op157 = 471, Op158 = 474, Op159 = 477, Op160 = 480, Op161 = 483,
+    Op162 = 486, Op163 = 489, Op164 = 492, Op165 = 495, Op166 = 498, Op167 = 501,
+    Op168 = 504, Op169 = 507, Op170 = 510, Op171 = 513, Op172 = 516, Op173 = 519,
+    Op174 = 522, Op175 = 525, Op176 = 528, Op177 = 531, Op178 = 534, Op179 = 537,
+    Op180 = 540, Op181 = 543, Op182 = 546, Op183 = 549, Op184 = 552, Op185 = 555,
+    Op186 = 558, Op187 = 561, Op188 = 564, Op189 = 567, Op190 = 570, Op191 = 573,
+    Op192 = 576, Op193 = 579, Op194 = 582, Op195 = 585, Op196 = 588, Op197 = 591,
+    Op198 = 594, Op199 = 597,
+}
+
+fn try_from_primitive(c: &mut Criterion) {
+    let mut group = c.benchmark_group("try_from_primitive");
+    group.bench_function("dense/derived", |b| {
+        b.iter(|| {
+            for number in 0..=u8::MAX {
+                black_box(Dense::try_from_primitive(black_box(number)).ok());
+            }
+        })
+    });
+    group.bench_function("dense/match", |b| {
+        b.iter(|| {
+            for number in 0..=u8::MAX {
+                black_box(dense_match(black_box(number)));
+            }
+        })
+    });
+    group.bench_function("sparse/derived", |b| {
+        b.iter(|| {
+            for number in 0..600 {
+                black_box(Sparse::try_from_primitive(black_box(number)).ok());
+            }
+        })
+    });
+    group.bench_function("sparse/match", |b| {
+        b.iter(|| {
+            for number in 0..600 {
+                black_box(sparse_match(black_box(number)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, try_from_primitive);
+criterion_main!(benches);
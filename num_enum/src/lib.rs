@@ -1,10 +1,368 @@
 #![no_std]
 
-pub use ::num_enum_derive::{IntoPrimitive, TryFromPrimitive, UnsafeFromPrimitive};
+#[cfg(feature = "std")]
+extern crate std;
+
+pub use ::num_enum_derive::{
+    BitFlags, EnumMeta, FromPrimitive, IntoPrimitive, TryFromPrimitive, UnsafeFromPrimitive,
+};
+
+#[cfg(feature = "serde")]
+pub use ::num_enum_derive::{DeserializePrimitive, SerializePrimitive};
 
 use ::core::fmt;
+use ::core::marker::PhantomData;
+
+pub trait FromPrimitive: Sized {
+    type Primitive: Copy + Eq + fmt::Debug;
+
+    const NAME: &'static str;
+
+    fn from_primitive(number: Self::Primitive) -> Self;
+}
+
+pub trait IntoPrimitive: Sized {
+    type Primitive: Copy + Eq;
+
+    fn into_primitive(self) -> Self::Primitive;
+}
 
 pub trait TryFromPrimitive: Sized {
     type Primitive: Copy + Eq + fmt::Debug;
-    fn try_from_primitive(number: Self::Primitive) -> Result<Self, ()>;
+
+    const NAME: &'static str;
+
+    fn try_from_primitive(number: Self::Primitive) -> Result<Self, TryFromPrimitiveError<Self>>;
+}
+
+// The `From` and `TryFrom` impls generated by the derives conflict, so an enum
+// derives at most one of the two traits; this lets `FromPrimitive` enums be
+// used wherever `TryFromPrimitive` is expected.
+impl<T: FromPrimitive> TryFromPrimitive for T {
+    type Primitive = T::Primitive;
+
+    const NAME: &'static str = T::NAME;
+
+    #[inline]
+    fn try_from_primitive(number: Self::Primitive) -> Result<Self, TryFromPrimitiveError<Self>> {
+        Ok(Self::from_primitive(number))
+    }
+}
+
+pub trait UnsafeFromPrimitive: Sized {
+    type Primitive: Copy + Eq;
+
+    /// Transmutes `number` into `Self`.
+    ///
+    /// # Safety
+    ///
+    ///   - `number` must represent a valid discriminant of `Self`
+    unsafe fn from_unchecked(number: Self::Primitive) -> Self;
+}
+
+/// Metadata about the variants of an enum.
+pub trait EnumMeta: Sized + 'static {
+    type Primitive: Copy + Ord;
+
+    /// Every variant, in declaration order.
+    const VARIANTS: &'static [Self];
+
+    /// The discriminant of every variant, in declaration order.
+    const DISCRIMINANTS: &'static [Self::Primitive];
+
+    /// The number of variants.
+    const COUNT: usize;
+
+    /// The smallest discriminant.
+    const MIN: Self::Primitive;
+
+    /// The largest discriminant.
+    const MAX: Self::Primitive;
+
+    /// The name of this variant.
+    fn name(&self) -> &'static str;
+
+    /// The variant with the given name, if any.
+    fn from_name(name: &str) -> Option<Self>;
+}
+
+/// An enum whose discriminants are single bits, combined into a set of flags.
+pub trait BitFlags: Sized {
+    type Primitive: Copy + Eq + fmt::Debug + fmt::LowerHex;
+
+    /// The type of sets of these flags.
+    type Set: Copy + Eq + From<Self> + IntoIterator<Item = Self>;
+
+    const NAME: &'static str;
+
+    /// The bits of every flag.
+    const ALL: Self::Primitive;
+}
+
+/// The error returned when some bits of a primitive value do not match any
+/// flag of `Flags`.
+pub struct TryFromBitsError<Flags: BitFlags> {
+    /// The value that was rejected.
+    pub bits: Flags::Primitive,
+}
+
+impl<Flags: BitFlags> TryFromBitsError<Flags> {
+    pub fn new(bits: Flags::Primitive) -> Self {
+        Self { bits }
+    }
+}
+
+impl<Flags: BitFlags> Copy for TryFromBitsError<Flags> {}
+
+impl<Flags: BitFlags> Clone for TryFromBitsError<Flags> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Flags: BitFlags> PartialEq for TryFromBitsError<Flags> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<Flags: BitFlags> Eq for TryFromBitsError<Flags> {}
+
+impl<Flags: BitFlags> fmt::Debug for TryFromBitsError<Flags> {
+    fn fmt(&self, stream: &'_ mut fmt::Formatter<'_>) -> fmt::Result {
+        stream
+            .debug_struct("TryFromBitsError")
+            .field("flags", &Flags::NAME)
+            .field("bits", &self.bits)
+            .finish()
+    }
+}
+
+impl<Flags: BitFlags> fmt::Display for TryFromBitsError<Flags> {
+    fn fmt(&self, stream: &'_ mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            stream,
+            "The value `{bits:#x}` has bits which don't match any flag of `{name}`",
+            name = Flags::NAME,
+            bits = self.bits,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Flags: BitFlags> ::std::error::Error for TryFromBitsError<Flags> {}
+
+/// The error returned when a primitive value does not match any discriminant
+/// of `Enum`.
+///
+/// `Number` is the type of the rejected value, which differs from
+/// `Enum::Primitive` for conversions added with
+/// `#[num_enum(try_from = [...])]`.
+pub struct TryFromPrimitiveError<
+    Enum: TryFromPrimitive,
+    Number = <Enum as TryFromPrimitive>::Primitive,
+> {
+    /// The value that was rejected.
+    pub number: Number,
+    _enum: PhantomData<fn() -> Enum>,
+}
+
+impl<Enum: TryFromPrimitive, Number> TryFromPrimitiveError<Enum, Number> {
+    pub fn new(number: Number) -> Self {
+        Self {
+            number,
+            _enum: PhantomData,
+        }
+    }
+}
+
+// Implemented by hand so as not to require `Enum` itself to implement these.
+impl<Enum: TryFromPrimitive, Number: Copy> Copy for TryFromPrimitiveError<Enum, Number> {}
+
+impl<Enum: TryFromPrimitive, Number: Copy> Clone for TryFromPrimitiveError<Enum, Number> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Enum: TryFromPrimitive, Number: PartialEq> PartialEq for TryFromPrimitiveError<Enum, Number> {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number
+    }
+}
+
+impl<Enum: TryFromPrimitive, Number: Eq> Eq for TryFromPrimitiveError<Enum, Number> {}
+
+impl<Enum: TryFromPrimitive, Number: fmt::Debug> fmt::Debug
+    for TryFromPrimitiveError<Enum, Number>
+{
+    fn fmt(&self, stream: &'_ mut fmt::Formatter<'_>) -> fmt::Result {
+        stream
+            .debug_struct("TryFromPrimitiveError")
+            .field("enum", &Enum::NAME)
+            .field("number", &self.number)
+            .finish()
+    }
+}
+
+impl<Enum: TryFromPrimitive, Number: fmt::Debug> fmt::Display
+    for TryFromPrimitiveError<Enum, Number>
+{
+    fn fmt(&self, stream: &'_ mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            stream,
+            "No discriminant in enum `{name}` matches the value `{input:?}`",
+            name = Enum::NAME,
+            input = self.number,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Enum: TryFromPrimitive, Number: fmt::Debug> ::std::error::Error
+    for TryFromPrimitiveError<Enum, Number>
+{
+}
+
+#[doc(hidden)]
+pub mod __private {
+    //! Support code for the derives. Not public API.
+
+    #[cfg(feature = "serde")]
+    pub use ::serde;
+
+    /// A fixed-capacity string, to build compile-time error messages in
+    /// `const` contexts. Anything that doesn't fit is dropped.
+    pub struct ConstStr {
+        bytes: [u8; 256],
+        len: usize,
+    }
+
+    impl ConstStr {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            ConstStr {
+                bytes: [0; 256],
+                len: 0,
+            }
+        }
+
+        pub const fn push_str(self, s: &str) -> Self {
+            self.push_bytes(s.as_bytes())
+        }
+
+        const fn push_bytes(mut self, bytes: &[u8]) -> Self {
+            // Only ever push whole strings, so that `as_str` stays valid UTF-8.
+            if self.len + bytes.len() > self.bytes.len() {
+                return self;
+            }
+            let mut i = 0;
+            while i < bytes.len() {
+                self.bytes[self.len] = bytes[i];
+                self.len += 1;
+                i += 1;
+            }
+            self
+        }
+
+        pub const fn push_u128(self, mut number: u128) -> Self {
+            let mut digits = [0; 39];
+            let mut start = digits.len();
+            loop {
+                start -= 1;
+                digits[start] = b'0' + (number % 10) as u8;
+                number /= 10;
+                if number == 0 {
+                    break;
+                }
+            }
+            let mut this = self;
+            while start < digits.len() {
+                this = this.push_bytes(&[digits[start]]);
+                start += 1;
+            }
+            this
+        }
+
+        pub const fn push_i128(self, number: i128) -> Self {
+            let this = if number < 0 { self.push_str("-") } else { self };
+            this.push_u128(number.unsigned_abs())
+        }
+
+        pub const fn as_str(&self) -> &str {
+            // SAFETY: only whole `str`s and ASCII digits are ever pushed.
+            unsafe {
+                ::core::str::from_utf8_unchecked(::core::slice::from_raw_parts(
+                    self.bytes.as_ptr(),
+                    self.len,
+                ))
+            }
+        }
+    }
+
+    const fn str_eq(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    macro_rules! check_overlaps {
+        ($check_overlaps:ident, $int:ty, $push:ident) => {
+            /// Describes the first overlap between two of the
+            /// `(variant, start, end, is_alternative)` inclusive ranges of
+            /// values, if any.
+            ///
+            /// Pairs of discriminants are skipped, as rustc already rejects
+            /// those.
+            pub const fn $check_overlaps(entries: &[(&str, $int, $int, bool)]) -> Option<ConstStr> {
+                let mut i = 0;
+                while i < entries.len() {
+                    let (a, a_start, a_end, a_is_alternative) = entries[i];
+                    let mut j = i + 1;
+                    while j < entries.len() {
+                        let (b, b_start, b_end, b_is_alternative) = entries[j];
+                        if (a_is_alternative || b_is_alternative)
+                            && a_start <= b_end
+                            && b_start <= a_end
+                        {
+                            let value = if a_start > b_start { a_start } else { b_start };
+                            let message = if str_eq(a, b) {
+                                ConstStr::new()
+                                    .push_str("`")
+                                    .push_str(a)
+                                    .push_str("` matches the value `")
+                                    .$push(value)
+                                    .push_str("` more than once")
+                            } else {
+                                ConstStr::new()
+                                    .push_str("`")
+                                    .push_str(a)
+                                    .push_str("` and `")
+                                    .push_str(b)
+                                    .push_str("` both match the value `")
+                                    .$push(value)
+                                    .push_str("`")
+                            };
+                            return Some(message);
+                        }
+                        j += 1;
+                    }
+                    i += 1;
+                }
+                None
+            }
+        };
+    }
+
+    check_overlaps!(check_overlaps_u128, u128, push_u128);
+    check_overlaps!(check_overlaps_i128, i128, push_i128);
 }
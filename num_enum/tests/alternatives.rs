@@ -0,0 +1,59 @@
+use num_enum::{FromPrimitive, IntoPrimitive, TryFromPrimitive, TryFromPrimitiveError};
+use std::convert::TryInto;
+
+const FOUR: u8 = 4;
+
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+enum Register {
+    Disabled,
+    Enabled,
+    #[num_enum(alternatives = [3, FOUR + 1..=7])]
+    Reserved,
+}
+
+#[test]
+fn alternatives_are_accepted() {
+    let disabled: Result<Register, _> = 0u8.try_into();
+    assert_eq!(disabled, Ok(Register::Disabled));
+
+    for number in &[2u8, 3, 5, 6, 7] {
+        let reserved: Result<Register, _> = (*number).try_into();
+        assert_eq!(reserved, Ok(Register::Reserved));
+    }
+
+    let four: Result<Register, _> = 4u8.try_into();
+    assert_eq!(four, Err(TryFromPrimitiveError::new(4)));
+
+    let eight: Result<Register, _> = 8u8.try_into();
+    assert_eq!(eight, Err(TryFromPrimitiveError::new(8)));
+}
+
+#[test]
+fn into_primitive_uses_discriminant() {
+    let reserved: u8 = Register::Reserved.into();
+    assert_eq!(reserved, 2);
+}
+
+#[derive(Debug, Eq, PartialEq, FromPrimitive)]
+#[repr(i8)]
+enum Ordering {
+    #[num_enum(alternatives = [i8::MIN..=-2])]
+    Less = -1,
+    Equal,
+    #[num_enum(alternatives = [2..=100])]
+    Greater,
+    #[num_enum(default)]
+    Unknown = 101,
+}
+
+#[test]
+fn from_primitive_alternatives() {
+    assert_eq!(Ordering::from(-100i8), Ordering::Less);
+    assert_eq!(Ordering::from(-1i8), Ordering::Less);
+    assert_eq!(Ordering::from(0i8), Ordering::Equal);
+    assert_eq!(Ordering::from(1i8), Ordering::Greater);
+    assert_eq!(Ordering::from(100i8), Ordering::Greater);
+    assert_eq!(Ordering::from(101i8), Ordering::Unknown);
+    assert_eq!(Ordering::from(i8::MAX), Ordering::Unknown);
+}
@@ -0,0 +1,95 @@
+use ::core::convert::TryFrom;
+
+use num_enum::{BitFlags, TryFromBitsError};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, BitFlags)]
+#[repr(u32)]
+enum Permission {
+    Read = 1,
+    Write = 2,
+    Execute = 1 << 4,
+}
+
+#[test]
+fn operators() {
+    let read_write = Permission::Read | Permission::Write;
+    assert_eq!(read_write.bits(), 0b11);
+    assert_eq!((read_write & Permission::Write).bits(), 0b10);
+    assert_eq!((read_write & Permission::Execute), PermissionSet::empty());
+
+    let mut set = PermissionSet::empty();
+    set |= Permission::Execute;
+    set |= read_write;
+    assert_eq!(set, PermissionSet::all());
+    set &= Permission::Read | Permission::Execute;
+    assert_eq!(u32::from(set), 0b1_0001);
+}
+
+#[test]
+fn contains_insert_remove() {
+    let mut set = PermissionSet::from(Permission::Read);
+    assert!(set.contains(Permission::Read));
+    assert!(!set.contains(Permission::Read | Permission::Write));
+
+    set.insert(Permission::Write);
+    assert!(set.contains(Permission::Read | Permission::Write));
+    assert_eq!(set.len(), 2);
+
+    set.remove(Permission::Read);
+    assert!(!set.contains(Permission::Read));
+    set.remove(Permission::Write);
+    assert!(set.is_empty());
+}
+
+#[test]
+fn iteration() {
+    let set = Permission::Execute | Permission::Read;
+    let flags: Vec<_> = set.iter().collect();
+    assert_eq!(flags, vec![Permission::Read, Permission::Execute]);
+    assert_eq!(set.iter().len(), 2);
+    assert_eq!(set.into_iter().collect::<PermissionSet>(), set);
+    assert_eq!(
+        format!("{:?}", PermissionSet::all()),
+        "PermissionSet(Read | Write | Execute)"
+    );
+    assert_eq!(format!("{:?}", PermissionSet::empty()), "PermissionSet()");
+}
+
+#[test]
+fn from_bits() {
+    assert_eq!(
+        PermissionSet::try_from(0b1_0010),
+        Ok(Permission::Write | Permission::Execute)
+    );
+    assert_eq!(
+        PermissionSet::try_from(0b0100),
+        Err(TryFromBitsError::new(0b0100))
+    );
+    assert_eq!(
+        PermissionSet::try_from(0b0101).unwrap_err().to_string(),
+        "The value `0x5` has bits which don't match any flag of `Permission`"
+    );
+    assert_eq!(
+        PermissionSet::from_bits_truncate(0xffff_ffff),
+        PermissionSet::all()
+    );
+    assert_eq!(<Permission as BitFlags>::ALL, 0b1_0011);
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, BitFlags)]
+#[repr(i8)]
+enum Signed {
+    Low = 1,
+    High = i8::MIN,
+}
+
+#[test]
+fn signed() {
+    let set = Signed::High | Signed::Low;
+    assert_eq!(set.bits(), i8::MIN | 1);
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![Signed::Low, Signed::High]
+    );
+    assert_eq!(SignedSet::from_bits(2), None);
+}
@@ -0,0 +1,36 @@
+use ::std::convert::TryFrom;
+
+use num_enum::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
+
+// rustc removes variants whose `#[cfg(...)]` is false before derives see the
+// enum, so they take no part in the conversions.
+#[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    #[cfg(any())]
+    Gated,
+    One,
+}
+
+#[test]
+fn compiled_out_variants_are_skipped() {
+    assert_eq!(u8::from(Numbers::One), 1);
+    assert_eq!(Numbers::try_from(1), Ok(Numbers::One));
+    assert!(Numbers::try_from(2).is_err());
+}
+
+#[derive(Debug, Eq, PartialEq, FromPrimitive)]
+#[repr(u8)]
+enum WithDefault {
+    Zero,
+    #[cfg(not(any()))]
+    #[num_enum(default)]
+    Unknown,
+}
+
+#[test]
+fn compiled_in_default() {
+    assert_eq!(WithDefault::from(0), WithDefault::Zero);
+    assert_eq!(WithDefault::from(7), WithDefault::Unknown);
+}
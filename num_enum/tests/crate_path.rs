@@ -0,0 +1,45 @@
+use ::core::convert::TryFrom;
+
+/// Stands in for a crate which re-exports `num_enum` to its users.
+mod facade {
+    pub use ::num_enum::*;
+}
+
+use facade::{EnumMeta, FromPrimitive, IntoPrimitive, TryFromPrimitive, UnsafeFromPrimitive};
+
+#[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive, UnsafeFromPrimitive, EnumMeta)]
+#[num_enum(crate = "crate::facade")]
+#[repr(u8)]
+enum Enum {
+    Zero,
+    #[num_enum(alternatives = [2, 3])]
+    One,
+    Four = 4,
+}
+
+#[test]
+fn derives_through_reexport() {
+    assert_eq!(u8::from(Enum::Four), 4);
+    assert_eq!(Enum::try_from(3), Ok(Enum::One));
+    assert_eq!(
+        Enum::try_from(5),
+        Err(facade::TryFromPrimitiveError::new(5))
+    );
+    assert_eq!(unsafe { Enum::from_unchecked(0) }, Enum::Zero);
+    assert_eq!(Enum::from_name("Four"), Some(Enum::Four));
+}
+
+#[derive(Debug, Eq, PartialEq, FromPrimitive)]
+#[num_enum(crate = "facade")]
+#[repr(u8)]
+enum WithDefault {
+    Zero,
+    #[num_enum(default)]
+    Other,
+}
+
+#[test]
+fn relative_path() {
+    assert_eq!(WithDefault::from(0u8), WithDefault::Zero);
+    assert_eq!(WithDefault::from(7u8), WithDefault::Other);
+}
@@ -0,0 +1,168 @@
+use num_enum::EnumMeta;
+
+const ONE: i8 = 1;
+
+#[derive(Debug, Eq, PartialEq, EnumMeta)]
+#[repr(i8)]
+enum Direction {
+    Up = ONE,
+    Down = -1,
+    #[num_enum(alternatives = [3])]
+    Left = -3,
+    Right,
+}
+
+#[test]
+fn variants_and_discriminants() {
+    assert_eq!(
+        Direction::VARIANTS,
+        &[
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right
+        ]
+    );
+    assert_eq!(Direction::DISCRIMINANTS, &[1, -1, -3, -2]);
+    assert_eq!(Direction::COUNT, 4);
+}
+
+#[test]
+fn min_and_max() {
+    assert_eq!(Direction::MIN, -3);
+    assert_eq!(Direction::MAX, 1);
+}
+
+#[derive(Debug, Eq, PartialEq, EnumMeta)]
+#[repr(u8)]
+enum Single {
+    Only = 7,
+}
+
+#[test]
+fn single_variant() {
+    assert_eq!(Single::VARIANTS, &[Single::Only]);
+    assert_eq!(Single::COUNT, 1);
+    assert_eq!(Single::MIN, 7);
+    assert_eq!(Single::MAX, 7);
+}
+
+fn count<E: EnumMeta>() -> usize {
+    E::VARIANTS.len()
+}
+
+#[test]
+fn generic_code() {
+    assert_eq!(count::<Direction>(), Direction::COUNT);
+}
+
+#[test]
+fn names() {
+    assert_eq!(Direction::Up.name(), "Up");
+    assert_eq!(Direction::from_name("Left"), Some(Direction::Left));
+    assert_eq!(Direction::from_name("left"), None);
+}
+
+#[test]
+fn iter_in_discriminant_order() {
+    let sorted: Vec<_> = Direction::iter().collect();
+    assert_eq!(
+        sorted,
+        [
+            Direction::Left,
+            Direction::Right,
+            Direction::Down,
+            Direction::Up
+        ]
+    );
+
+    let mut iter = Direction::iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next_back(), Some(Direction::Up));
+    assert_eq!(iter.next(), Some(Direction::Left));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(
+        iter.rev().collect::<Vec<_>>(),
+        [Direction::Down, Direction::Right]
+    );
+
+    assert_eq!(Single::iter().collect::<Vec<_>>(), [Single::Only]);
+}
+
+#[test]
+fn next_and_prev() {
+    assert_eq!(Direction::Left.next(), Some(Direction::Right));
+    assert_eq!(Direction::Down.next(), Some(Direction::Up));
+    assert_eq!(Direction::Up.next(), None);
+    assert_eq!(Direction::Up.prev(), Some(Direction::Down));
+    assert_eq!(Direction::Left.prev(), None);
+
+    assert_eq!(Direction::Up.next_wrapping(), Direction::Left);
+    assert_eq!(Direction::Left.prev_wrapping(), Direction::Up);
+    assert_eq!(Direction::Right.next_wrapping(), Direction::Down);
+    assert_eq!(Single::Only.next_wrapping(), Single::Only);
+    assert_eq!(Single::Only.prev_wrapping(), Single::Only);
+}
+
+#[derive(Debug, Eq, PartialEq, EnumMeta)]
+#[repr(u8)]
+#[num_enum(rename_all = "snake_case")]
+enum LogLevel {
+    Error,
+    WarningOrWorse,
+    #[num_enum(rename = "information")]
+    Info,
+    HTTPTrace,
+}
+
+#[test]
+fn renamed() {
+    let names: Vec<_> = LogLevel::VARIANTS.iter().map(EnumMeta::name).collect();
+    assert_eq!(
+        names,
+        ["error", "warning_or_worse", "information", "http_trace"]
+    );
+
+    for variant in LogLevel::VARIANTS {
+        assert_eq!(LogLevel::from_name(variant.name()).as_ref(), Some(variant));
+    }
+    assert_eq!(LogLevel::from_name("Info"), None);
+    assert_eq!(LogLevel::from_name("info"), None);
+}
+
+macro_rules! rename_all {
+    ($($module:ident: $rule:literal => [$($name:literal),*],)*) => {
+        $(
+            mod $module {
+                use num_enum::EnumMeta;
+
+                #[derive(EnumMeta)]
+                #[repr(u8)]
+                #[num_enum(rename_all = $rule)]
+                enum Renamed {
+                    Simple,
+                    TwoWords,
+                    HTTPServer,
+                    With2Digits,
+                }
+
+                #[test]
+                fn rename_all() {
+                    let names: Vec<_> = Renamed::VARIANTS.iter().map(EnumMeta::name).collect();
+                    assert_eq!(names, [$($name),*]);
+                }
+            }
+        )*
+    };
+}
+
+rename_all! {
+    lowercase: "lowercase" => ["simple", "twowords", "httpserver", "with2digits"],
+    uppercase: "UPPERCASE" => ["SIMPLE", "TWOWORDS", "HTTPSERVER", "WITH2DIGITS"],
+    pascal_case: "PascalCase" => ["Simple", "TwoWords", "HttpServer", "With2Digits"],
+    camel_case: "camelCase" => ["simple", "twoWords", "httpServer", "with2Digits"],
+    snake_case: "snake_case" => ["simple", "two_words", "http_server", "with2_digits"],
+    screaming_snake_case: "SCREAMING_SNAKE_CASE" => ["SIMPLE", "TWO_WORDS", "HTTP_SERVER", "WITH2_DIGITS"],
+    kebab_case: "kebab-case" => ["simple", "two-words", "http-server", "with2-digits"],
+    screaming_kebab_case: "SCREAMING-KEBAB-CASE" => ["SIMPLE", "TWO-WORDS", "HTTP-SERVER", "WITH2-DIGITS"],
+}
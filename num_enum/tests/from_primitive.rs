@@ -0,0 +1,171 @@
+use num_enum::{FromPrimitive, TryFromPrimitive, TryFromPrimitiveError};
+use std::convert::TryInto;
+
+#[derive(Debug, Eq, PartialEq, FromPrimitive)]
+#[repr(u8)]
+enum Opcode {
+    Nop,
+    Load = 4,
+    Store,
+    #[num_enum(default)]
+    Unknown = 0xff,
+}
+
+#[test]
+fn known_values() {
+    assert_eq!(Opcode::from(0u8), Opcode::Nop);
+    assert_eq!(Opcode::from(4u8), Opcode::Load);
+    assert_eq!(Opcode::from(5u8), Opcode::Store);
+    assert_eq!(Opcode::from(0xffu8), Opcode::Unknown);
+}
+
+#[test]
+fn unknown_values_map_to_default() {
+    assert_eq!(Opcode::from(1u8), Opcode::Unknown);
+    assert_eq!(Opcode::from(6u8), Opcode::Unknown);
+    assert_eq!(Opcode::from_primitive(0xfeu8), Opcode::Unknown);
+}
+
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
+#[repr(i16)]
+enum Sign {
+    Negative = -1,
+    #[num_enum(default)]
+    Zero,
+    Positive,
+}
+
+#[test]
+fn try_from_primitive_honors_default() {
+    let negative: Result<Sign, TryFromPrimitiveError<Sign>> = (-1i16).try_into();
+    assert_eq!(negative, Ok(Sign::Negative));
+
+    let positive: Result<Sign, _> = 1i16.try_into();
+    assert_eq!(positive, Ok(Sign::Positive));
+
+    let unknown: Result<Sign, _> = 7i16.try_into();
+    assert_eq!(unknown, Ok(Sign::Zero));
+}
+
+#[test]
+fn implements_try_from_primitive() {
+    fn decode<T: TryFromPrimitive<Primitive = u8>>(
+        number: u8,
+    ) -> Result<T, TryFromPrimitiveError<T>> {
+        T::try_from_primitive(number)
+    }
+
+    assert_eq!(decode::<Opcode>(4), Ok(Opcode::Load));
+    assert_eq!(decode::<Opcode>(1), Ok(Opcode::Unknown));
+    assert_eq!(<Opcode as TryFromPrimitive>::NAME, "Opcode");
+}
+
+#[derive(Debug, Eq, PartialEq, FromPrimitive)]
+#[repr(u8)]
+#[num_enum(try_from = [u32, i8])]
+enum Register {
+    Zero,
+    One,
+    #[num_enum(default)]
+    Other,
+}
+
+#[test]
+fn try_from_other_types() {
+    use std::convert::TryFrom;
+
+    assert_eq!(Register::try_from(1u32), Ok(Register::One));
+    assert_eq!(Register::try_from(7u32), Ok(Register::Other));
+    assert_eq!(
+        Register::try_from(256u32),
+        Err(TryFromPrimitiveError::new(256u32)),
+    );
+    assert_eq!(Register::try_from(0i8), Ok(Register::Zero));
+    assert_eq!(
+        Register::try_from(-1i8),
+        Err(TryFromPrimitiveError::new(-1i8))
+    );
+}
+
+mod catch_all {
+    use num_enum::{FromPrimitive, IntoPrimitive, TryFromPrimitive};
+    use std::convert::TryInto;
+
+    #[derive(Debug, Eq, PartialEq, FromPrimitive, IntoPrimitive)]
+    #[repr(u8)]
+    enum Opcode {
+        Nop,
+        Load = 4,
+        Store,
+        #[num_enum(catch_all)]
+        Other(u8),
+    }
+
+    #[test]
+    fn unknown_values_are_retained() {
+        assert_eq!(Opcode::from(0u8), Opcode::Nop);
+        assert_eq!(Opcode::from(5u8), Opcode::Store);
+        assert_eq!(Opcode::from(1u8), Opcode::Other(1));
+        assert_eq!(Opcode::from(0xffu8), Opcode::Other(0xff));
+    }
+
+    #[test]
+    fn round_trips() {
+        for number in 0..=u8::MAX {
+            let opcode = Opcode::from(number);
+            let back: u8 = opcode.into();
+            assert_eq!(back, number);
+        }
+    }
+
+    #[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+    #[repr(i32)]
+    enum Status {
+        Failure = -1,
+        #[num_enum(catch_all)]
+        Code(i32) = 0,
+        Success,
+    }
+
+    #[test]
+    fn try_from_primitive_honors_catch_all() {
+        let failure: Result<Status, _> = (-1i32).try_into();
+        assert_eq!(failure, Ok(Status::Failure));
+
+        // The discriminant of the catch-all variant is not claimed by it.
+        let zero: Result<Status, _> = 0i32.try_into();
+        assert_eq!(zero, Ok(Status::Code(0)));
+
+        let success: Result<Status, _> = 1i32.try_into();
+        assert_eq!(success, Ok(Status::Success));
+
+        let other: Result<Status, _> = 404i32.try_into();
+        assert_eq!(other, Ok(Status::Code(404)));
+
+        let other: i32 = Status::Code(404).into();
+        assert_eq!(other, 404);
+        let success: i32 = Status::Success.into();
+        assert_eq!(success, 1);
+    }
+}
+
+mod catch_all_const {
+    use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+    #[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+    #[repr(u8)]
+    enum Opcode {
+        Nop,
+        #[num_enum(catch_all)]
+        Other(u8),
+    }
+
+    const OTHER: u8 = Opcode::Other(9).to_primitive();
+    const DECODED: Option<Opcode> = Opcode::try_from_primitive_const(9);
+
+    #[test]
+    fn usable_in_const_items() {
+        assert_eq!(OTHER, 9);
+        assert_eq!(DECODED, Some(Opcode::Other(9)));
+    }
+}
@@ -0,0 +1,124 @@
+use ::core::convert::TryFrom;
+use ::core::marker::PhantomData;
+
+use num_enum::{
+    EnumMeta, FromPrimitive, IntoPrimitive, TryFromPrimitive, TryFromPrimitiveError,
+    UnsafeFromPrimitive,
+};
+
+#[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive, UnsafeFromPrimitive, EnumMeta)]
+#[repr(u8)]
+enum Tag<T> {
+    A,
+    B,
+    _Phantom(PhantomData<T>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct UserId;
+
+#[test]
+fn phantom_typed() {
+    assert_eq!(u8::from(Tag::<UserId>::B), 1);
+    assert_eq!(Tag::<UserId>::_Phantom(PhantomData).to_primitive(), 2);
+
+    assert_eq!(Tag::<UserId>::try_from(0), Ok(Tag::A));
+    assert_eq!(Tag::<UserId>::try_from(2), Ok(Tag::_Phantom(PhantomData)));
+    assert_eq!(
+        Tag::<UserId>::try_from(3),
+        Err(TryFromPrimitiveError::new(3))
+    );
+
+    assert_eq!(unsafe { Tag::<UserId>::from_unchecked(1) }, Tag::B);
+
+    assert_eq!(Tag::<UserId>::DISCRIMINANTS, &[0, 1, 2]);
+    assert_eq!(Tag::<UserId>::VARIANTS[2].name(), "_Phantom");
+    assert_eq!(Tag::<UserId>::from_name("A"), Some(Tag::A));
+}
+
+#[derive(Debug, Eq, PartialEq, FromPrimitive, IntoPrimitive)]
+#[repr(i16)]
+enum Bounded<'a, T>
+where
+    T: Clone + 'a,
+{
+    Start = -1,
+    Middle {
+        _data: PhantomData<&'a T>,
+    },
+    #[num_enum(default)]
+    End = 5,
+}
+
+#[test]
+fn lifetimes_and_where_clauses() {
+    assert_eq!(Bounded::<String>::from(-1), Bounded::Start);
+    assert_eq!(
+        Bounded::<String>::from(0),
+        Bounded::Middle { _data: PhantomData }
+    );
+    assert_eq!(Bounded::<String>::from(1), Bounded::End);
+    let middle: Bounded<String> = Bounded::Middle { _data: PhantomData };
+    assert_eq!(i16::from(middle), 0);
+}
+
+#[derive(Debug, Eq, PartialEq, FromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+enum GenericCatchAll<T> {
+    Zero,
+    _Phantom(PhantomData<T>),
+    #[num_enum(catch_all)]
+    Other(u8),
+}
+
+#[test]
+fn generic_catch_all() {
+    assert_eq!(GenericCatchAll::<UserId>::from(0), GenericCatchAll::Zero);
+    assert_eq!(
+        GenericCatchAll::<UserId>::from(7),
+        GenericCatchAll::Other(7)
+    );
+    assert_eq!(u8::from(GenericCatchAll::<UserId>::Other(7)), 7);
+}
+
+#[derive(Debug, Eq, PartialEq, FromPrimitive)]
+#[repr(u8)]
+enum TupleDefault<T> {
+    A,
+    #[num_enum(default)]
+    Other(PhantomData<T>),
+}
+
+#[test]
+fn phantom_tuple_default() {
+    assert_eq!(TupleDefault::<UserId>::from(0), TupleDefault::A);
+    assert_eq!(
+        TupleDefault::<UserId>::from(1),
+        TupleDefault::Other(PhantomData)
+    );
+    assert_eq!(
+        TupleDefault::<UserId>::from(9),
+        TupleDefault::Other(PhantomData)
+    );
+}
+
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
+#[repr(u8)]
+enum StructDefault<T> {
+    A,
+    #[num_enum(default)]
+    Other {
+        _marker: PhantomData<T>,
+    },
+}
+
+#[test]
+fn phantom_struct_default() {
+    assert_eq!(StructDefault::<UserId>::try_from(0), Ok(StructDefault::A));
+    assert_eq!(
+        StructDefault::<UserId>::try_from(9),
+        Ok(StructDefault::Other {
+            _marker: PhantomData
+        })
+    );
+}
@@ -20,9 +20,21 @@ mod into_primitive {
         let two: u8 = SimpleNumber::Two.into();
         assert_eq!(two, 2u8);
     }
+
+    #[test]
+    fn trait_in_generic_code() {
+        fn write<E: IntoPrimitive>(buffer: &mut Vec<E::Primitive>, value: E) {
+            buffer.push(value.into_primitive());
+        }
+
+        let mut buffer = Vec::new();
+        write(&mut buffer, SimpleNumber::Two);
+        write(&mut buffer, SimpleNumber::Zero);
+        assert_eq!(buffer, vec![2u8, 0u8]);
+    }
 }
 
-use num_enum::TryFromPrimitive;
+use num_enum::{TryFromPrimitive, TryFromPrimitiveError};
 use std::convert::TryInto;
 
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -39,7 +51,22 @@ fn simple() {
     assert_eq!(zero, Ok(SimpleNumber::Zero));
 
     let three: Result<SimpleNumber, _> = 3u8.try_into();
-    assert_eq!(three, Err(()),);
+    assert_eq!(three, Err(TryFromPrimitiveError::new(3)),);
+}
+
+#[test]
+fn error_reports_value_and_enum_name() {
+    let three: Result<SimpleNumber, _> = 3u8.try_into();
+    let error = three.unwrap_err();
+    assert_eq!(error.number, 3u8);
+    assert_eq!(
+        format!("{}", error),
+        "No discriminant in enum `SimpleNumber` matches the value `3`",
+    );
+    assert_eq!(
+        format!("{:?}", error),
+        "TryFromPrimitiveError { enum: \"SimpleNumber\", number: 3 }",
+    );
 }
 
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -56,13 +83,13 @@ fn even() {
     assert_eq!(zero, Ok(EvenNumber::Zero));
 
     let one: Result<EvenNumber, _> = 1u8.try_into();
-    assert_eq!(one, Err(()));
+    assert_eq!(one, Err(TryFromPrimitiveError::new(1)));
 
     let two: Result<EvenNumber, _> = 2u8.try_into();
     assert_eq!(two, Ok(EvenNumber::Two));
 
     let three: Result<EvenNumber, _> = 3u8.try_into();
-    assert_eq!(three, Err(()));
+    assert_eq!(three, Err(TryFromPrimitiveError::new(3)));
 
     let four: Result<EvenNumber, _> = 4u8.try_into();
     assert_eq!(four, Ok(EvenNumber::Four));
@@ -86,7 +113,7 @@ fn skipped_value() {
     assert_eq!(one, Ok(SkippedNumber::One));
 
     let two: Result<SkippedNumber, _> = 2u8.try_into();
-    assert_eq!(two, Err(()));
+    assert_eq!(two, Err(TryFromPrimitiveError::new(2)));
 
     let three: Result<SkippedNumber, _> = 3u8.try_into();
     assert_eq!(three, Ok(SkippedNumber::Three));
@@ -113,7 +140,7 @@ fn wrong_order() {
     assert_eq!(one, Ok(WrongOrderNumber::One));
 
     let two: Result<WrongOrderNumber, _> = 2u8.try_into();
-    assert_eq!(two, Err(()));
+    assert_eq!(two, Err(TryFromPrimitiveError::new(2)));
 
     let three: Result<WrongOrderNumber, _> = 3u8.try_into();
     assert_eq!(three, Ok(WrongOrderNumber::Three));
@@ -122,8 +149,11 @@ fn wrong_order() {
     assert_eq!(four, Ok(WrongOrderNumber::Four));
 }
 
-#[cfg(feature = "complex-expression")]
+#[cfg(feature = "complex-expressions")]
 mod complex {
+    use num_enum::{TryFromPrimitive, TryFromPrimitiveError};
+    use std::convert::TryInto;
+
     const ONE: u8 = 1;
 
     #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -150,7 +180,7 @@ mod complex {
         assert_eq!(two, Ok(DifferentValuesNumber::Two));
 
         let three: Result<DifferentValuesNumber, _> = 3u8.try_into();
-        assert_eq!(three, Err(()));
+        assert_eq!(three, Err(TryFromPrimitiveError::new(3)));
 
         let four: Result<DifferentValuesNumber, _> = 4u8.try_into();
         assert_eq!(four, Ok(DifferentValuesNumber::Four));
@@ -183,7 +213,7 @@ fn missing_trailing_comma() {
     assert_eq!(one, Ok(MissingTrailingCommaNumber::One));
 
     let two: Result<MissingTrailingCommaNumber, _> = 2u8.try_into();
-    assert_eq!(two, Err(()));
+    assert_eq!(two, Err(TryFromPrimitiveError::new(2)));
 }
 
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -204,7 +234,7 @@ fn ignores_extra_attributes() {
     assert_eq!(one, Ok(ExtraAttributes::One));
 
     let two: Result<ExtraAttributes, _> = 2u8.try_into();
-    assert_eq!(two, Err(()));
+    assert_eq!(two, Err(TryFromPrimitiveError::new(2)));
 }
 
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -223,7 +253,7 @@ fn visibility_is_fine() {
     assert_eq!(one, Ok(VisibleNumber::One));
 
     let two: Result<VisibleNumber, _> = 2u8.try_into();
-    assert_eq!(two, Err(()));
+    assert_eq!(two, Err(TryFromPrimitiveError::new(2)));
 }
 
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -242,7 +272,7 @@ fn error_variant_is_allowed() {
     assert_eq!(err, Ok(HasErrorVariant::Error));
 
     let unknown: Result<HasErrorVariant, _> = 2u8.try_into();
-    assert_eq!(unknown, Err(()));
+    assert_eq!(unknown, Err(TryFromPrimitiveError::new(2)));
 }
 
 use num_enum::UnsafeFromPrimitive;
@@ -267,3 +297,200 @@ fn has_unsafe_from_primitive_number() {
         );
     }
 }
+
+#[test]
+fn unsafe_from_primitive_trait() {
+    unsafe fn from_byte<E: UnsafeFromPrimitive<Primitive = u8>>(byte: u8) -> E {
+        E::from_unchecked(byte)
+    }
+
+    assert_eq!(
+        unsafe { from_byte::<HasUnsafeFromPrimitiveNumber>(1) },
+        HasUnsafeFromPrimitiveNumber::One
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "`2` is not a valid discriminant of `HasUnsafeFromPrimitiveNumber`")]
+fn unsafe_from_primitive_checks_discriminant_in_debug_builds() {
+    unsafe {
+        HasUnsafeFromPrimitiveNumber::from_unchecked(2_u8);
+    }
+}
+
+mod const_fn {
+    use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+    #[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+    #[repr(u8)]
+    enum Opcode {
+        Nop,
+        Load = 4,
+        #[num_enum(alternatives = [6])]
+        Store,
+    }
+
+    const LOAD: u8 = Opcode::Load.to_primitive();
+
+    const DECODED: [Option<Opcode>; 8] = {
+        let mut decoded = [None, None, None, None, None, None, None, None];
+        let mut number = 0;
+        while number < decoded.len() {
+            decoded[number] = Opcode::try_from_primitive_const(number as u8);
+            number += 1;
+        }
+        decoded
+    };
+
+    #[test]
+    fn usable_in_const_items() {
+        assert_eq!(LOAD, 4);
+        assert_eq!(
+            DECODED,
+            [
+                Some(Opcode::Nop),
+                None,
+                None,
+                None,
+                Some(Opcode::Load),
+                Some(Opcode::Store),
+                Some(Opcode::Store),
+                None,
+            ]
+        );
+    }
+}
+
+mod dense {
+    use num_enum::{FromPrimitive, TryFromPrimitive, TryFromPrimitiveError};
+    use std::convert::TryFrom;
+
+    #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
+    #[repr(u8)]
+    enum Offset {
+        Twelve = 12,
+        Ten = 10,
+        Eleven,
+        Thirteen = 13,
+    }
+
+    #[test]
+    fn contiguous_out_of_order() {
+        for number in 0..=u8::MAX {
+            let expected = match number {
+                10 => Ok(Offset::Ten),
+                11 => Ok(Offset::Eleven),
+                12 => Ok(Offset::Twelve),
+                13 => Ok(Offset::Thirteen),
+                _ => Err(TryFromPrimitiveError::new(number)),
+            };
+            assert_eq!(Offset::try_from(number), expected);
+        }
+    }
+
+    #[derive(Debug, Eq, PartialEq, FromPrimitive)]
+    #[repr(i8)]
+    enum Signed {
+        MinusTwo = -2,
+        MinusOne,
+        Zero,
+        One,
+        #[num_enum(default)]
+        Two,
+    }
+
+    #[test]
+    fn signed_with_default() {
+        for number in i8::MIN..=i8::MAX {
+            let expected = match number {
+                -2 => Signed::MinusTwo,
+                -1 => Signed::MinusOne,
+                0 => Signed::Zero,
+                1 => Signed::One,
+                _ => Signed::Two,
+            };
+            assert_eq!(Signed::from(number), expected);
+        }
+    }
+
+    #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
+    #[repr(i128)]
+    enum Extremes {
+        Min = i128::MIN,
+        Max = i128::MAX,
+    }
+
+    #[test]
+    fn sparse_across_whole_range() {
+        assert_eq!(Extremes::try_from(i128::MIN), Ok(Extremes::Min));
+        assert_eq!(Extremes::try_from(i128::MAX), Ok(Extremes::Max));
+        assert_eq!(Extremes::try_from(0), Err(TryFromPrimitiveError::new(0)));
+    }
+}
+
+mod other_widths {
+    use num_enum::{IntoPrimitive, TryFromPrimitive, TryFromPrimitiveError};
+    use std::convert::TryFrom;
+
+    #[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+    #[repr(u8)]
+    #[num_enum(try_from = [u16, u32, i8, i64, usize])]
+    enum Opcode {
+        Nop,
+        Halt = 0xff,
+    }
+
+    #[test]
+    fn try_from_wider() {
+        assert_eq!(Opcode::try_from(0xff_u32), Ok(Opcode::Halt));
+        assert_eq!(Opcode::try_from(0_i64), Ok(Opcode::Nop));
+        assert_eq!(Opcode::try_from(0_usize), Ok(Opcode::Nop));
+
+        // Out of range of the `repr`, or not a discriminant.
+        let error: TryFromPrimitiveError<Opcode, u32> = Opcode::try_from(0x1ff_u32).unwrap_err();
+        assert_eq!(error, TryFromPrimitiveError::new(0x1ff));
+        assert_eq!(
+            Opcode::try_from(-1_i64),
+            Err(TryFromPrimitiveError::new(-1))
+        );
+        assert_eq!(Opcode::try_from(1_u16), Err(TryFromPrimitiveError::new(1)));
+        assert_eq!(
+            Opcode::try_from(511_u16).unwrap_err().to_string(),
+            "No discriminant in enum `Opcode` matches the value `511`"
+        );
+    }
+
+    #[test]
+    fn try_from_signed() {
+        assert_eq!(Opcode::try_from(0_i8), Ok(Opcode::Nop));
+        assert_eq!(Opcode::try_from(-1_i8), Err(TryFromPrimitiveError::new(-1)));
+    }
+
+    #[test]
+    fn from_lossless() {
+        assert_eq!(u16::from(Opcode::Halt), 0xff);
+        assert_eq!(u32::from(Opcode::Halt), 0xff);
+        assert_eq!(i64::from(Opcode::Halt), 0xff);
+        assert_eq!(usize::from(Opcode::Halt), 0xff);
+        // No `From<Opcode> for i8`, which would be lossy.
+    }
+}
+
+mod implicit_discriminants {
+    use num_enum::{TryFromPrimitive, TryFromPrimitiveError};
+    use std::convert::TryFrom;
+
+    #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
+    #[repr(i8)]
+    enum Edge {
+        BeforeMax = 126,
+        Max,
+    }
+
+    #[test]
+    fn up_to_max() {
+        assert_eq!(Edge::try_from(127), Ok(Edge::Max));
+        assert_eq!(Edge::try_from(-128), Err(TryFromPrimitiveError::new(-128)));
+    }
+}
@@ -0,0 +1,83 @@
+use num_enum::{
+    FromPrimitive, IntoPrimitive, TryFromPrimitive, TryFromPrimitiveError, UnsafeFromPrimitive,
+};
+use std::convert::TryInto;
+
+// `repr(C, u8)` is only meaningful (and allowed) for enums with fields.
+#[derive(Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
+#[repr(C, u8)]
+enum SharedWithC {
+    Zero,
+    One,
+    #[num_enum(catch_all)]
+    Other(u8),
+}
+
+#[test]
+fn repr_c_with_integer() {
+    let one: u8 = SharedWithC::One.into();
+    assert_eq!(one, 1);
+
+    assert_eq!(SharedWithC::from(0u8), SharedWithC::Zero);
+    assert_eq!(SharedWithC::from(7u8), SharedWithC::Other(7));
+}
+
+#[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(C)]
+#[repr(i16)]
+enum SeparateAttributes {
+    Negative = -1,
+    Zero,
+    #[num_enum(catch_all)]
+    Other(i16),
+}
+
+#[test]
+fn separate_repr_attributes() {
+    let negative: i16 = SeparateAttributes::Negative.into();
+    assert_eq!(negative, -1);
+
+    let zero: Result<SeparateAttributes, _> = 0i16.try_into();
+    assert_eq!(zero, Ok(SeparateAttributes::Zero));
+
+    let other: Result<SeparateAttributes, _> = 5i16.try_into();
+    assert_eq!(other, Ok(SeparateAttributes::Other(5)));
+}
+
+#[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u16, align(4))]
+enum Aligned {
+    Zero,
+    One,
+}
+
+#[test]
+fn repr_with_align() {
+    assert_eq!(std::mem::align_of::<Aligned>(), 4);
+
+    let one: u16 = Aligned::One.into();
+    assert_eq!(one, 1);
+
+    let two: Result<Aligned, _> = 2u16.try_into();
+    assert_eq!(two, Err(TryFromPrimitiveError::new(2)));
+}
+
+#[derive(Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive, UnsafeFromPrimitive)]
+#[repr(C)]
+#[num_enum(primitive = "i32")]
+enum CEnum {
+    Zero,
+    One,
+    Big = 100_000,
+}
+
+#[test]
+fn repr_c_with_primitive_override() {
+    let big: i32 = CEnum::Big.into();
+    assert_eq!(big, 100_000);
+
+    let one: Result<CEnum, _> = 1i32.try_into();
+    assert_eq!(one, Ok(CEnum::One));
+
+    assert_eq!(unsafe { CEnum::from_unchecked(0) }, CEnum::Zero);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "serde")]
+
+use num_enum::{DeserializePrimitive, FromPrimitive, SerializePrimitive, TryFromPrimitive};
+
+#[derive(Debug, Eq, PartialEq, SerializePrimitive, DeserializePrimitive, TryFromPrimitive)]
+#[repr(u16)]
+enum Opcode {
+    Nop,
+    Load = 0x10,
+    #[num_enum(alternatives = [0x21..=0x2f])]
+    Store = 0x20,
+}
+
+#[test]
+fn round_trip() {
+    assert_eq!(serde_json::to_string(&Opcode::Load).unwrap(), "16");
+    assert_eq!(
+        serde_json::to_string(&[Opcode::Nop, Opcode::Store]).unwrap(),
+        "[0,32]"
+    );
+    assert_eq!(serde_json::from_str::<Opcode>("16").unwrap(), Opcode::Load);
+    assert_eq!(serde_json::from_str::<Opcode>("34").unwrap(), Opcode::Store);
+}
+
+#[test]
+fn invalid_value() {
+    let error = serde_json::from_str::<Opcode>("3").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "No discriminant in enum `Opcode` matches the value `3`"
+    );
+
+    // Out of range of the primitive: reported by serde itself.
+    assert!(serde_json::from_str::<Opcode>("-1").is_err());
+}
+
+#[derive(Debug, Eq, PartialEq, SerializePrimitive, DeserializePrimitive, TryFromPrimitive)]
+#[repr(i8)]
+enum Signed {
+    Negative = -1,
+    #[num_enum(catch_all)]
+    Other(i8),
+}
+
+#[test]
+fn catch_all() {
+    assert_eq!(serde_json::to_string(&Signed::Negative).unwrap(), "-1");
+    assert_eq!(serde_json::to_string(&Signed::Other(5)).unwrap(), "5");
+    assert_eq!(
+        serde_json::from_str::<Signed>("5").unwrap(),
+        Signed::Other(5)
+    );
+}
+
+#[derive(Debug, Eq, PartialEq, SerializePrimitive, DeserializePrimitive, TryFromPrimitive)]
+#[repr(u8)]
+enum Tag<T> {
+    A,
+    _Phantom(::core::marker::PhantomData<T>),
+}
+
+#[test]
+fn generic() {
+    assert_eq!(serde_json::to_string(&Tag::<String>::A).unwrap(), "0");
+    assert_eq!(serde_json::from_str::<Tag<String>>("0").unwrap(), Tag::A);
+}
+
+#[derive(Debug, Eq, PartialEq, SerializePrimitive, DeserializePrimitive, FromPrimitive)]
+#[repr(u8)]
+enum Defaulted {
+    Zero,
+    #[num_enum(default)]
+    Unknown,
+}
+
+#[test]
+fn from_primitive() {
+    assert_eq!(
+        serde_json::from_str::<Defaulted>("0").unwrap(),
+        Defaulted::Zero
+    );
+    assert_eq!(
+        serde_json::from_str::<Defaulted>("7").unwrap(),
+        Defaulted::Unknown
+    );
+}
@@ -0,0 +1,5 @@
+#[test]
+fn trybuild() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/try_build/compile_fail/*.rs");
+}
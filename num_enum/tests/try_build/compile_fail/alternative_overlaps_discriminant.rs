@@ -0,0 +1,13 @@
+const ONE: u8 = 1;
+
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    One = ONE,
+    Two = ONE + 1u8,
+    #[num_enum(alternatives = [4, 2])]
+    Three,
+}
+
+fn main() {}
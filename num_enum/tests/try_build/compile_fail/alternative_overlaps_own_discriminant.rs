@@ -0,0 +1,9 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(u64)]
+enum Numbers {
+    Zero,
+    #[num_enum(alternatives = [0xFFFF_FFFF_FFFF, 1])]
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[derive(num_enum::FromPrimitive)]
+#[repr(i16)]
+enum Numbers {
+    #[num_enum(alternatives = [-100..=-10])]
+    Negative = -1,
+    #[num_enum(default)]
+    Zero,
+    #[num_enum(alternatives = [-20..=-5])]
+    Positive,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(num_enum::BitFlags)]
+#[repr(u8)]
+enum Flags<T> {
+    A = 1,
+    B = 2,
+    _Phantom(::core::marker::PhantomData<T>) = 4,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(num_enum::BitFlags)]
+#[repr(u8)]
+enum Flags {
+    A = 1,
+    B = 6,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(num_enum::BitFlags)]
+#[repr(u8)]
+enum Flags {
+    A = 1,
+    #[num_enum(alternatives = [4])]
+    B = 2,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[derive(num_enum::BitFlags)]
+#[repr(u8)]
+enum Flags {
+    A = 1,
+    B = 2,
+    #[num_enum(catch_all)]
+    Other(u8),
+}
+
+fn main() {}
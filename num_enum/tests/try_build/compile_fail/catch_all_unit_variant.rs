@@ -0,0 +1,9 @@
+#[derive(num_enum::FromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    #[num_enum(catch_all)]
+    Other,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[derive(num_enum::FromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    #[num_enum(default)]
+    Zero,
+    #[num_enum(catch_all)]
+    Other(u8),
+}
+
+fn main() {}
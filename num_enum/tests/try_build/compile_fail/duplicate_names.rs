@@ -0,0 +1,10 @@
+#[derive(num_enum::EnumMeta)]
+#[repr(u8)]
+#[num_enum(rename_all = "lowercase")]
+enum Numbers {
+    Zero,
+    #[num_enum(rename = "zero")]
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(num_enum::FromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
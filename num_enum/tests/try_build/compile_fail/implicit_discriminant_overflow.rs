@@ -0,0 +1,10 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(C)]
+#[num_enum(primitive = "u8")]
+enum Numbers {
+    Zero,
+    Max = 255,
+    Overflowing,
+}
+
+fn main() {}
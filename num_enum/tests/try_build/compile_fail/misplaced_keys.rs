@@ -0,0 +1,13 @@
+use std::marker::PhantomData;
+
+#[derive(num_enum::TryFromPrimitive)]
+#[num_enum(default, try_from = [u16])]
+#[repr(u8)]
+enum Numbers<T> {
+    Zero,
+    #[num_enum(alternatives = [3], primitive = "u8")]
+    One,
+    Two(#[num_enum(default)] PhantomData<T>),
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(num_enum::TryFromPrimitive)]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[derive(num_enum::FromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    #[num_enum(catch_all)]
+    One(u8),
+    #[num_enum(catch_all)]
+    Two(u8),
+}
+
+fn main() {}
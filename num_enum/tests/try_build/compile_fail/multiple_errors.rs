@@ -0,0 +1,21 @@
+use core::convert::TryFrom;
+use num_enum::EnumMeta;
+
+#[derive(Debug, num_enum::IntoPrimitive, num_enum::TryFromPrimitive, num_enum::EnumMeta)]
+#[num_enum(rename_all = "shouting", try_from = [f32])]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    #[num_enum(defualt, rename = "zero")]
+    One { value: u8 },
+    #[num_enum(alternatives = [3..=4])]
+    Two,
+    #[num_enum(catch_all)]
+    Other(u8),
+}
+
+fn main() {
+    let _ = Numbers::try_from(1u8);
+    let _: u8 = Numbers::Two.into();
+    let _ = Numbers::VARIANTS;
+}
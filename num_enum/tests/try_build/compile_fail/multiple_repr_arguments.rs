@@ -0,0 +1,8 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(u8, u16)]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(C)]
+#[num_enum(primitive = "::core::primitive::i32")]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[derive(num_enum::FromPrimitive)]
+#[repr(C, u8)]
+#[num_enum(primitive = "i32")]
+enum Numbers {
+    Zero,
+    One,
+    #[num_enum(catch_all)]
+    Other(u8),
+}
+
+fn main() {}
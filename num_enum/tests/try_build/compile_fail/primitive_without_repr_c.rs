@@ -0,0 +1,8 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[num_enum(primitive = "i32")]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(num_enum::FromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    #[num_enum(catch_all, rename = "other")]
+    Other(u8),
+}
+
+fn main() {}
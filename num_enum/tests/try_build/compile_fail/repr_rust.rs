@@ -0,0 +1,7 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(Rust)]
+enum Numbers {
+    Zero,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(transparent)]
+enum Numbers {
+    Zero,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(num_enum::IntoPrimitive)]
+#[repr(u8, packed)]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
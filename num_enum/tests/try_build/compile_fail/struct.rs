@@ -0,0 +1,6 @@
+#[derive(num_enum::TryFromPrimitive)]
+struct Number {
+    value: u8,
+}
+
+fn main() {}
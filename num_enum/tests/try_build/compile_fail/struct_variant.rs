@@ -0,0 +1,8 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    One { value: u8 },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(u8)]
+#[num_enum(try_from = [u16, u16])]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
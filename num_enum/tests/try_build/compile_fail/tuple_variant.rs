@@ -0,0 +1,8 @@
+#[derive(num_enum::IntoPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    One(u8),
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+#[derive(num_enum::IntoPrimitive)]
+union Number {
+    value: u8,
+}
+
+fn main() {}
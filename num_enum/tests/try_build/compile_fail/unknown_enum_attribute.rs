@@ -0,0 +1,9 @@
+#[derive(num_enum::TryFromPrimitive)]
+#[repr(u8)]
+#[num_enum(repr = "u8")]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(num_enum::EnumMeta)]
+#[repr(u8)]
+#[num_enum(rename_all = "Title Case")]
+enum Numbers {
+    Zero,
+    One,
+}
+
+fn main() {}
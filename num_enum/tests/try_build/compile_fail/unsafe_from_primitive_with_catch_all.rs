@@ -0,0 +1,9 @@
+#[derive(num_enum::UnsafeFromPrimitive)]
+#[repr(u8)]
+enum Numbers {
+    Zero,
+    #[num_enum(catch_all)]
+    Other(u8),
+}
+
+fn main() {}
@@ -1,19 +1,23 @@
 extern crate proc_macro;
-use ::core::iter::FromIterator;
 use ::proc_macro::TokenStream;
-use ::proc_macro2::Span;
-use ::quote::quote;
+use ::proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use ::quote::{format_ident, quote, quote_spanned, ToTokens};
 use ::syn::{
+    bracketed,
+    ext::IdentExt,
     parse::{Parse, ParseStream},
-    parse_macro_input, parse_quote, Data, DeriveInput, Error, Expr, Ident, LitInt, LitStr, Meta,
-    Result,
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Data, DeriveInput, Error, Expr, Fields, Generics, Ident, LitInt, LitStr, Meta, Path,
+    Result, Token, Type, Visibility,
 };
 
 macro_rules! die {
     ($span:expr=>
         $msg:expr
     ) => (
-        return Err(Error::new($span, $msg));
+        return Err(Error::new($span, $msg))
     );
 
     (
@@ -23,6 +27,139 @@ macro_rules! die {
     );
 }
 
+/// Errors collected while deriving, so that all of them are reported at once.
+/// Derives emit them alongside a best-effort impl, which keeps uses of the
+/// impl from failing too.
+#[derive(Default)]
+struct Errors(Option<Error>);
+
+impl Errors {
+    fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn push_new<T: ::core::fmt::Display>(&mut self, span: Span, message: T) {
+        self.push(Error::new(span, message));
+    }
+}
+
+impl ToTokens for Errors {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        if let Some(error) = &self.0 {
+            tokens.extend(error.to_compile_error());
+        }
+    }
+}
+
+/// Parses the comma-separated keys of a `#[num_enum(...)]` attribute. Keys
+/// which fail to parse are reported, and parsing resumes after the next comma.
+fn parse_keys<T: Parse>(attr: &Attribute, errors: &mut Errors) -> Vec<T> {
+    let mut keys = Vec::new();
+    let result = attr.parse_args_with(|input: ParseStream| {
+        let skip_to_comma = |input: ParseStream| -> Result<()> {
+            while !input.is_empty() && !input.peek(Token![,]) {
+                input.parse::<TokenTree>()?;
+            }
+            Ok(())
+        };
+        while !input.is_empty() {
+            match input.parse() {
+                Ok(key) => keys.push(key),
+                Err(error) => {
+                    errors.push(error);
+                    skip_to_comma(input)?;
+                }
+            }
+            if !input.is_empty() && !input.peek(Token![,]) {
+                errors.push(input.error("expected `,`"));
+                skip_to_comma(input)?;
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(())
+    });
+    if let Err(error) = result {
+        errors.push(error);
+    }
+    keys
+}
+
+/// The `repr`s which can be converted to and from.
+const INTEGER_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+fn integer_types_list() -> String {
+    INTEGER_TYPES
+        .iter()
+        .map(|ty| format!("`{}`", ty))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A const expression evaluating to the smallest (`op` being `<`) or largest
+/// (`op` being `>`) element of the non-empty slice `values`.
+fn const_extremum(values: TokenStream2, op: TokenStream2) -> TokenStream2 {
+    quote! {
+        {
+            let values: &[_] = #values;
+            let mut extremum = values[0];
+            let mut i = 1;
+            while i < values.len() {
+                if values[i] #op extremum {
+                    extremum = values[i];
+                }
+                i += 1;
+            }
+            extremum
+        }
+    }
+}
+
+/// Whether every value of the integer type `from` fits in the integer type
+/// `to`, i.e. whether `to` implements `From<from>`.
+fn is_lossless(from: &str, to: &str) -> bool {
+    fn bits(ty: &str) -> Option<u32> {
+        ty[1..].parse().ok()
+    }
+
+    match (from, to) {
+        _ if from == to => true,
+        // Pointer-sized integers are at least 16 bits wide.
+        ("u8" | "u16", "usize") | ("u8" | "i8" | "i16", "isize") => true,
+        ("usize" | "isize", _) | (_, "usize" | "isize") => false,
+        _ => match (bits(from), bits(to)) {
+            (Some(from_bits), Some(to_bits)) => {
+                let from_signed = from.starts_with('i');
+                let to_signed = to.starts_with('i');
+                match (from_signed, to_signed) {
+                    (false, false) | (true, true) => from_bits <= to_bits,
+                    (false, true) => from_bits < to_bits,
+                    (true, false) => false,
+                }
+            }
+            _ => false,
+        },
+    }
+}
+
+/// An expression of any type standing in for a value which can't be produced
+/// because of an error which has been reported. Unlike `unreachable!()`, it
+/// doesn't cause "unreachable code" warnings on top of the error.
+fn placeholder() -> TokenStream2 {
+    quote! {
+        match ::core::option::Option::None {
+            ::core::option::Option::Some(value) => value,
+            ::core::option::Option::None => ::core::unreachable!(),
+        }
+    }
+}
+
 fn literal(i: u64) -> Expr {
     let literal = LitInt::new(&i.to_string(), Span::call_site());
     parse_quote! {
@@ -30,10 +167,383 @@ fn literal(i: u64) -> Expr {
     }
 }
 
+mod kw {
+    ::syn::custom_keyword!(alternatives);
+    ::syn::custom_keyword!(catch_all);
+    ::syn::custom_keyword!(primitive);
+    ::syn::custom_keyword!(rename);
+    ::syn::custom_keyword!(rename_all);
+    ::syn::custom_keyword!(try_from);
+}
+
+/// The keys of `#[num_enum(...)]` attributes on the enum itself.
+const ENUM_KEYS: &[&str] = &["crate", "primitive", "rename_all", "try_from"];
+
+/// The keys of `#[num_enum(...)]` attributes on variants.
+const VARIANT_KEYS: &[&str] = &["default", "catch_all", "alternatives", "rename"];
+
+/// An error for a key which is only valid elsewhere, which reads better than
+/// the list of keys expected here.
+fn misplaced_key(input: ParseStream, keys: &[&str], message: &str) -> Option<Error> {
+    let key = input.fork().call(Ident::parse_any).ok()?;
+    if keys.iter().any(|other| key == other) {
+        Some(Error::new(key.span(), format!("`{}` {}", key, message)))
+    } else {
+        None
+    }
+}
+
+/// A single key of a `#[num_enum(...)]` attribute on the enum itself.
+enum EnumAttribute {
+    Crate(LitStr),
+    Primitive(LitStr),
+    RenameAll(LitStr),
+    TryFrom(Vec<Ident>),
+}
+
+impl Parse for EnumAttribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![crate]) {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            Ok(EnumAttribute::Crate(input.parse()?))
+        } else if lookahead.peek(kw::primitive) {
+            input.parse::<kw::primitive>()?;
+            input.parse::<Token![=]>()?;
+            Ok(EnumAttribute::Primitive(input.parse()?))
+        } else if lookahead.peek(kw::rename_all) {
+            input.parse::<kw::rename_all>()?;
+            input.parse::<Token![=]>()?;
+            Ok(EnumAttribute::RenameAll(input.parse()?))
+        } else if lookahead.peek(kw::try_from) {
+            input.parse::<kw::try_from>()?;
+            input.parse::<Token![=]>()?;
+            let content;
+            bracketed!(content in input);
+            let types = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+            Ok(EnumAttribute::TryFrom(types.into_iter().collect()))
+        } else if let Some(error) = misplaced_key(
+            input,
+            VARIANT_KEYS,
+            "can only be used on variants, not on the enum itself",
+        ) {
+            Err(error)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// A single key of a `#[num_enum(...)]` attribute on a variant.
+enum VariantAttribute {
+    Default(Token![default]),
+    CatchAll(kw::catch_all),
+    Alternatives(Vec<Alternative>),
+    Rename(LitStr),
+}
+
+impl Parse for VariantAttribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![default]) {
+            Ok(VariantAttribute::Default(input.parse()?))
+        } else if lookahead.peek(kw::catch_all) {
+            Ok(VariantAttribute::CatchAll(input.parse()?))
+        } else if lookahead.peek(kw::alternatives) {
+            input.parse::<kw::alternatives>()?;
+            input.parse::<Token![=]>()?;
+            let content;
+            bracketed!(content in input);
+            let alternatives = Punctuated::<Alternative, Token![,]>::parse_terminated(&content)?;
+            Ok(VariantAttribute::Alternatives(
+                alternatives.into_iter().collect(),
+            ))
+        } else if lookahead.peek(kw::rename) {
+            input.parse::<kw::rename>()?;
+            input.parse::<Token![=]>()?;
+            Ok(VariantAttribute::Rename(input.parse()?))
+        } else if let Some(error) = misplaced_key(
+            input,
+            ENUM_KEYS,
+            "can only be used on the enum itself, not on variants",
+        ) {
+            Err(error)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// An extra value (`3`) or inclusive range of values (`5..=7`) accepted for a
+/// variant, on top of its discriminant.
+struct Alternative {
+    start: Expr,
+    end: Option<Expr>,
+}
+
+impl Parse for Alternative {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // Without `syn/full`, `Expr` doesn't know about ranges, so split on
+        // `..=` by hand.
+        fn bound(input: ParseStream) -> Result<Expr> {
+            let mut tokens = TokenStream2::new();
+            while !input.is_empty() && !input.peek(Token![,]) && !input.peek(Token![..=]) {
+                tokens.extend(::core::iter::once(input.parse::<TokenTree>()?));
+            }
+            if tokens.is_empty() {
+                return Err(input.error("Expected an alternative value"));
+            }
+            ::syn::parse2(tokens)
+        }
+
+        let start = bound(input)?;
+        let end = if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            Some(bound(input)?)
+        } else {
+            None
+        };
+        Ok(Alternative { start, end })
+    }
+}
+
+/// The case conversion applied to variant names by
+/// `#[num_enum(rename_all = "...")]`.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    const ALL: &'static [(&'static str, RenameRule)] = &[
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("PascalCase", RenameRule::Pascal),
+        ("camelCase", RenameRule::Camel),
+        ("snake_case", RenameRule::Snake),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+        ("kebab-case", RenameRule::Kebab),
+        ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+    ];
+
+    fn parse(lit: &LitStr) -> Result<Self> {
+        let value = lit.value();
+        match RenameRule::ALL.iter().find(|(name, _)| *name == value) {
+            Some((_, rule)) => Ok(*rule),
+            None => die!(lit.span()=>
+                format!(
+                    "Unknown `rename_all` rule; expected one of {}",
+                    RenameRule::ALL
+                        .iter()
+                        .map(|(name, _)| format!("`{}`", name))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            ),
+        }
+    }
+
+    fn apply(self, variant: &str) -> String {
+        fn capitalize(word: &str) -> String {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        }
+
+        let words = || split_words(variant).into_iter();
+        match self {
+            RenameRule::Lower => variant.to_lowercase(),
+            RenameRule::Upper => variant.to_uppercase(),
+            RenameRule::Pascal => words().map(|word| capitalize(&word)).collect(),
+            RenameRule::Camel => words()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(&word)
+                    }
+                })
+                .collect(),
+            RenameRule::Snake => words()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            RenameRule::ScreamingSnake => words()
+                .map(|word| word.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            RenameRule::Kebab => words()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("-"),
+            RenameRule::ScreamingKebab => words()
+                .map(|word| word.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("-"),
+        }
+    }
+}
+
+/// Splits an identifier such as `HttpServer`, `HTTPServer` or `http_server`
+/// into its words.
+fn split_words(ident: &str) -> Vec<String> {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !word.is_empty() {
+                words.push(::core::mem::take(&mut word));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !word.is_empty() {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            // Start a new word at `aB`, and at the `Se` of `HTTPServer`.
+            if !previous.is_uppercase() || next_is_lowercase {
+                words.push(::core::mem::take(&mut word));
+            }
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Whether `ty` looks like `PhantomData<..>`.
+fn is_phantom(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => {
+            type_path.qself.is_none()
+                && type_path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "PhantomData")
+        }
+        _ => false,
+    }
+}
+
+struct VariantInfo {
+    ident: Ident,
+    /// The name used by `EnumMeta::name` and `EnumMeta::from_name`.
+    name: String,
+    discriminant: Expr,
+    alternatives: Vec<Alternative>,
+    /// Either `Fields::Unit`, or fields which are all `PhantomData`.
+    fields: Fields,
+}
+
+impl VariantInfo {
+    fn is_unit(&self) -> bool {
+        matches!(self.fields, Fields::Unit)
+    }
+
+    /// A pattern matching this variant, whatever its fields.
+    fn pattern(&self, name: &Ident) -> TokenStream2 {
+        let ident = &self.ident;
+        match self.fields {
+            Fields::Unit => quote!(#name::#ident),
+            Fields::Unnamed(_) => quote!(#name::#ident(..)),
+            Fields::Named(_) => quote!(#name::#ident { .. }),
+        }
+    }
+
+    /// An expression constructing this variant, with every field set to
+    /// `PhantomData`.
+    fn constructor(&self, name: &Ident) -> TokenStream2 {
+        let ident = &self.ident;
+        match &self.fields {
+            Fields::Unit => quote!(#name::#ident),
+            Fields::Unnamed(fields) => {
+                let phantoms = fields
+                    .unnamed
+                    .iter()
+                    .map(|_| quote!(::core::marker::PhantomData));
+                quote!(#name::#ident(#(#phantoms),*))
+            }
+            Fields::Named(fields) => {
+                let field_idents = fields.named.iter().map(|field| &field.ident);
+                quote!(#name::#ident { #(#field_idents: ::core::marker::PhantomData),* })
+            }
+        }
+    }
+
+    /// Names of the intermediate consts holding the bounds of each
+    /// alternative, as `(start, end)`.
+    fn alternative_idents(&self) -> Vec<(Ident, Option<Ident>)> {
+        (0..self.alternatives.len())
+            .map(|i| {
+                let start = format_ident!("{}__num_enum_alternative_{}", self.ident, i);
+                let end = self.alternatives[i]
+                    .end
+                    .as_ref()
+                    .map(|_| format_ident!("{}__num_enum_alternative_{}_end", self.ident, i));
+                (start, end)
+            })
+            .collect()
+    }
+}
+
+/// The path to `num_enum` when no `#[num_enum(crate = "...")]` is given: the
+/// name under which the invoking crate depends on it, so that renaming the
+/// dependency in `Cargo.toml` keeps working.
+fn default_crate_path() -> Path {
+    use ::proc_macro_crate::{crate_name, FoundCrate};
+
+    match crate_name("num_enum") {
+        Ok(FoundCrate::Name(name)) => {
+            let ident = Ident::new(&name, Span::call_site());
+            parse_quote!(::#ident)
+        }
+        // `Itself` is also what integration tests and examples of `num_enum`
+        // get, where the crate is available as an extern crate.
+        Ok(FoundCrate::Itself) | Err(_) => parse_quote!(::num_enum),
+    }
+}
+
 struct EnumInfo {
+    vis: Visibility,
     name: Ident,
+    generics: Generics,
     repr: Ident,
-    value_expressions_to_enum_keys: Vec<(Expr, Ident)>,
+    /// Whether the enum has the same size as `repr`, which isn't the case with
+    /// `repr(align(..))`, and may not be with `#[num_enum(primitive = "...")]`.
+    same_size_as_repr: bool,
+    /// The extra integer types listed in `#[num_enum(try_from = [...])]`.
+    try_from: Vec<Ident>,
+    /// The path to the `num_enum` crate used in the generated code.
+    krate: Path,
+    variants: Vec<VariantInfo>,
+    /// The index in `variants` of the `#[num_enum(default)]` variant.
+    default: Option<usize>,
+    catch_all: Option<Ident>,
+    /// Whether a variant marked `#[num_enum(default)]` or
+    /// `#[num_enum(catch_all)]` was rejected, which makes a missing fallback
+    /// variant unsurprising.
+    rejected_fallback: bool,
+    /// Whether variants were left out of `variants` because of errors.
+    incomplete: bool,
+    /// Problems found in the enum, to be reported by every derive.
+    errors: Errors,
 }
 
 impl Parse for EnumInfo {
@@ -52,157 +562,1089 @@ impl Parse for EnumInfo {
                 die!(span => "Expected enum");
             };
 
+            // Problems are recorded rather than returned, falling back to
+            // something sensible so that the rest of the enum still gets
+            // checked.
+            let mut errors = Errors::default();
+            // Whether a problem with the type of the discriminants has already
+            // been reported, in which case it isn't reported as missing too.
+            let mut repr_reported = false;
+            let mut krate = None;
+            let mut primitive = None;
+            let mut rename_all = None;
+            let mut try_from = Vec::<Ident>::new();
+            for attr in &input.attrs {
+                if !attr.path.is_ident("num_enum") {
+                    continue;
+                }
+                for attribute in parse_keys::<EnumAttribute>(attr, &mut errors) {
+                    match attribute {
+                        EnumAttribute::Crate(lit) => match lit.parse::<Path>() {
+                            Ok(path) => krate = Some(path),
+                            Err(error) => errors.push(error),
+                        },
+                        EnumAttribute::Primitive(lit) => match lit.parse::<Ident>() {
+                            Ok(ident) if INTEGER_TYPES.iter().any(|ty| ident == ty) => {
+                                primitive = Some(ident);
+                            }
+                            _ => {
+                                errors.push_new(
+                                    lit.span(),
+                                    format!("Expected an integer type ({})", integer_types_list(),),
+                                );
+                                repr_reported = true;
+                            }
+                        },
+                        EnumAttribute::RenameAll(lit) => match RenameRule::parse(&lit) {
+                            Ok(rule) => rename_all = Some(rule),
+                            Err(error) => errors.push(error),
+                        },
+                        EnumAttribute::TryFrom(types) => {
+                            for ty in types {
+                                if !INTEGER_TYPES.iter().any(|integer| ty == integer) {
+                                    errors.push_new(
+                                        ty.span(),
+                                        format!(
+                                            "Expected an integer type ({})",
+                                            integer_types_list(),
+                                        ),
+                                    );
+                                } else if try_from.contains(&ty) {
+                                    errors.push_new(
+                                        ty.span(),
+                                        format!("`{}` is listed more than once", ty),
+                                    );
+                                } else {
+                                    try_from.push(ty);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            let same_size_as_repr;
             let repr: Ident = {
-                let mut attrs = input.attrs.into_iter();
-                loop {
-                    if let Some(attr) = attrs.next() {
-                        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
-                            if let Some(ident) = meta_list.path.get_ident() {
-                                if ident == "repr" {
-                                    let mut nested = meta_list.nested.iter();
-                                    if nested.len() != 1 {
-                                        die!(ident.span()=>
-                                            "Expected exactly one `repr` argument"
-                                        );
-                                    }
-                                    let repr = nested.next().unwrap();
-                                    let repr: Ident = parse_quote! {
-                                        #repr
-                                    };
-                                    if repr == "C" {
-                                        die!(repr.span()=>
-                                            "repr(C) doesn't have a well defined size"
-                                        );
-                                    } else {
-                                        break repr;
-                                    }
+                let mut repr_c = None;
+                let mut aligned = false;
+                let mut integer = None;
+                for attr in &input.attrs {
+                    if !attr.path.is_ident("repr") {
+                        continue;
+                    }
+                    let arguments = match attr
+                        .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    {
+                        Ok(arguments) => arguments,
+                        Err(error) => {
+                            errors.push(error);
+                            repr_reported = true;
+                            continue;
+                        }
+                    };
+                    for argument in arguments {
+                        match argument {
+                            // Only affects the layout, not the discriminants.
+                            Meta::List(ref list) if list.path.is_ident("align") => {
+                                aligned = true;
+                            }
+                            Meta::Path(ref path) if path.is_ident("C") => {
+                                repr_c = Some(path.span());
+                            }
+                            Meta::Path(ref path)
+                                if INTEGER_TYPES.iter().any(|ty| path.is_ident(ty)) =>
+                            {
+                                if integer.is_some() {
+                                    errors.push_new(
+                                        path.span(),
+                                        "Expected exactly one integer `repr` argument",
+                                    );
+                                    // The size of the enum is then unclear, so don't
+                                    // rely on it, like with `align(..)`.
+                                    aligned = true;
+                                } else {
+                                    integer = path.get_ident().cloned();
                                 }
                             }
+                            _ => {
+                                errors.push_new(
+                                    argument.span(),
+                                    format!(
+                                        "Unsupported `repr` argument; expected an integer type ({}), optionally with `C` or `align(..)`",
+                                        integer_types_list(),
+                                    ),
+                                );
+                                repr_reported = true;
+                            }
                         }
-                    } else {
-                        die!("Missing `#[repr({Integer})]` attribute");
+                    }
+                }
+
+                // Without any integer type, `isize` is only a stand-in.
+                same_size_as_repr = !aligned && primitive.is_none() && integer.is_some();
+                match (integer, primitive) {
+                    (Some(integer), Some(primitive)) => {
+                        errors.push_new(
+                            primitive.span(),
+                            "`#[num_enum(primitive = \"...\")]` can only be used with a bare `#[repr(C)]`",
+                        );
+                        integer
+                    }
+                    (Some(integer), None) => integer,
+                    (None, Some(primitive)) => {
+                        if repr_c.is_none() {
+                            errors.push_new(
+                                primitive.span(),
+                                "`#[num_enum(primitive = \"...\")]` requires `#[repr(C)]`",
+                            );
+                        }
+                        primitive
+                    }
+                    (None, None) => {
+                        match repr_c {
+                            _ if repr_reported => {}
+                            Some(span) => errors.push_new(
+                                span,
+                                "repr(C) doesn't have a well defined size; use `#[repr(C, {Integer})]`, or pick one with `#[num_enum(primitive = \"{Integer}\")]`",
+                            ),
+                            None => errors.push_new(
+                                Span::call_site(),
+                                "Missing `#[repr({Integer})]` attribute",
+                            ),
+                        }
+                        // The type of discriminants without a `repr`.
+                        Ident::new("isize", Span::call_site())
                     }
                 }
             };
 
-            let mut next_discriminant = literal(0);
-            let value_expressions_to_enum_keys =
-                Vec::from_iter(data.variants.into_iter().map(|variant| {
-                    let disc = if let Some(d) = variant.discriminant {
-                        d.1
-                    } else {
-                        next_discriminant.clone()
-                    };
-                    let variant_ident = &variant.ident;
-                    next_discriminant = parse_quote! {
-                        #repr::wrapping_add(#variant_ident, 1)
+            if let Some(position) = try_from.iter().position(|ty| *ty == repr) {
+                let ty = try_from.remove(position);
+                errors.push_new(
+                    ty.span(),
+                    format!(
+                        "`{}` is already the `repr` of the enum, which is always converted from",
+                        ty,
+                    ),
+                );
+            }
+
+            let mut previous_discriminant: Option<Expr> = None;
+            let mut variants = Vec::with_capacity(data.variants.len());
+            let mut default = None;
+            let mut catch_all = None;
+            let mut rejected_fallback = false;
+            let mut incomplete = false;
+            for variant in data.variants {
+                for field in &variant.fields {
+                    for attr in &field.attrs {
+                        if attr.path.is_ident("num_enum") {
+                            errors.push_new(
+                                attr.path.span(),
+                                "`#[num_enum(...)]` can only be used on the enum and its variants",
+                            );
+                        }
+                    }
+                }
+                let disc = match (variant.discriminant, &previous_discriminant) {
+                    (Some(d), _) => d.1,
+                    (None, None) => literal(0),
+                    (None, Some(previous)) => {
+                        // rustc rejects overflowing implicit discriminants too,
+                        // but they mustn't silently wrap in the generated code.
+                        let message = format!(
+                            "The implicit discriminant of `{}` overflows `{}`",
+                            variant.ident, repr,
+                        );
+                        Expr::Verbatim(quote_spanned! {variant.ident.span()=>
+                            match #repr::checked_add(#previous, 1) {
+                                ::core::option::Option::Some(discriminant) => discriminant,
+                                ::core::option::Option::None => ::core::panic!(#message),
+                            }
+                        })
+                    }
+                };
+
+                let mut is_default = false;
+                let mut is_catch_all = false;
+                let mut extra_catch_all = false;
+                let mut alternatives = Vec::new();
+                let mut rename = None;
+                for attr in &variant.attrs {
+                    if !attr.path.is_ident("num_enum") {
+                        continue;
+                    }
+                    for attribute in parse_keys::<VariantAttribute>(attr, &mut errors) {
+                        match attribute {
+                            VariantAttribute::Default(keyword) => {
+                                if default.is_some() {
+                                    errors.push_new(
+                                        keyword.span(),
+                                        "Multiple variants marked `#[num_enum(default)]` found",
+                                    );
+                                } else {
+                                    is_default = true;
+                                    default = Some(variant.ident.clone());
+                                }
+                            }
+                            VariantAttribute::CatchAll(keyword) => {
+                                if catch_all.is_some() || is_catch_all {
+                                    errors.push_new(
+                                        keyword.span(),
+                                        "Multiple variants marked `#[num_enum(catch_all)]` found",
+                                    );
+                                    extra_catch_all = true;
+                                } else {
+                                    is_catch_all = true;
+                                }
+                            }
+                            VariantAttribute::Alternatives(values) => {
+                                alternatives.extend(values);
+                            }
+                            VariantAttribute::Rename(lit) => {
+                                rename = Some(lit);
+                            }
+                        }
+                    }
+                }
+
+                // Rejected variants are left out of `variants`, and matches on
+                // the enum get a wildcard arm instead.
+                let mut rejected = extra_catch_all;
+                if is_catch_all {
+                    if is_default {
+                        errors.push_new(
+                            variant.ident.span(),
+                            "A variant can't be both `default` and `catch_all`",
+                        );
+                        is_default = false;
+                        default = None;
+                    }
+                    if !alternatives.is_empty() {
+                        errors.push_new(
+                            variant.ident.span(),
+                            "A `catch_all` variant can't have alternatives",
+                        );
+                    }
+                    if let Some(rename) = &rename {
+                        errors.push_new(rename.span(), "A `catch_all` variant can't be renamed");
+                    }
+                    let message = format!(
+                        "`#[num_enum(catch_all)]` variants must have exactly one unnamed field of type `{}`",
+                        repr,
+                    );
+                    let field_is_repr = match &variant.fields {
+                        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                            let field_type = &fields.unnamed[0].ty;
+                            let field_is_repr = match field_type {
+                                Type::Path(type_path) => {
+                                    type_path.qself.is_none() && type_path.path.is_ident(&repr)
+                                }
+                                _ => false,
+                            };
+                            if !field_is_repr {
+                                errors.push_new(
+                                    field_type.span(),
+                                    format!("Expected `{}` (the `repr` of the enum)", repr),
+                                );
+                            }
+                            field_is_repr
+                        }
+                        Fields::Unit => {
+                            errors.push_new(variant.ident.span(), message);
+                            false
+                        }
+                        _ => {
+                            errors.push_new(variant.fields.span(), message);
+                            false
+                        }
                     };
-                    (disc, variant.ident)
-                }));
+                    is_catch_all = field_is_repr;
+                    rejected = !is_catch_all;
+                    rejected_fallback |= rejected;
+                } else if !rejected && !variant.fields.iter().all(|field| is_phantom(&field.ty)) {
+                    errors.push_new(
+                        variant.fields.span(),
+                        "Only unit variants and variants whose fields are all `PhantomData` are supported, apart from a single `#[num_enum(catch_all)]` variant",
+                    );
+                    rejected = true;
+                }
+                if is_default && rejected {
+                    default = None;
+                    rejected_fallback = true;
+                }
+                if is_catch_all {
+                    catch_all = Some(variant.ident.clone());
+                }
+                incomplete |= rejected;
+
+                // The catch-all and rejected variants don't get an entry of
+                // their own, but still occupy a discriminant which the next
+                // variant may follow.
+                if is_catch_all || rejected {
+                    previous_discriminant = Some(disc);
+                    continue;
+                }
+
+                let variant_ident = &variant.ident;
+                previous_discriminant = Some(parse_quote!(#variant_ident));
+                let variant_name = match (rename, rename_all) {
+                    (Some(rename), _) => rename.value(),
+                    (None, Some(rule)) => rule.apply(&variant.ident.to_string()),
+                    (None, None) => variant.ident.to_string(),
+                };
+                if let Some(other) = variants
+                    .iter()
+                    .find(|other: &&VariantInfo| other.name == variant_name)
+                {
+                    errors.push_new(
+                        variant.ident.span(),
+                        format!(
+                            "`{}` and `{}` both have the name \"{}\"",
+                            other.ident, variant.ident, variant_name,
+                        ),
+                    );
+                }
+                variants.push(VariantInfo {
+                    ident: variant.ident,
+                    name: variant_name,
+                    discriminant: disc,
+                    alternatives,
+                    fields: variant.fields,
+                });
+            }
+
+            if let (Some(default), Some(_)) = (&default, &catch_all) {
+                errors.push_new(
+                    default.span(),
+                    "`#[num_enum(default)]` and `#[num_enum(catch_all)]` can't be used together",
+                );
+            }
+            let default = default
+                .and_then(|default| variants.iter().position(|variant| variant.ident == default));
 
             EnumInfo {
+                vis: input.vis,
                 name,
+                generics: input.generics,
                 repr,
-                value_expressions_to_enum_keys,
+                same_size_as_repr,
+                try_from,
+                krate: krate.unwrap_or_else(default_crate_path),
+                variants,
+                default,
+                catch_all,
+                rejected_fallback,
+                incomplete,
+                errors,
             }
         })
     }
 }
 
-/// Implements `Into<Primitive>` for a `#[repr(Primitive)] enum`.
-///
-/// (It actually implements `From<Enum> for Primitive`)
-///
-/// ## Allows turning an enum into a primitive.
-#[proc_macro_derive(IntoPrimitive)]
-pub fn derive_into_primitive(input: TokenStream) -> TokenStream {
-    let EnumInfo { name, repr, .. } = parse_macro_input!(input as EnumInfo);
+impl EnumInfo {
+    fn is_signed(&self) -> bool {
+        self.repr.to_string().starts_with('i')
+    }
 
-    TokenStream::from(quote! {
-        impl From<#name> for #repr {
-            #[inline]
-            fn from (enum_value: #name) -> Self
+    /// Whether a valid discriminant can be transmuted into the enum, which
+    /// requires it to be fieldless and not generic.
+    fn can_transmute(&self) -> bool {
+        self.same_size_as_repr
+            && !self.incomplete
+            && self.catch_all.is_none()
+            && self.generics.params.is_empty()
+            && self.variants.iter().all(VariantInfo::is_unit)
+    }
+
+    /// Defines one intermediate const per variant, so that enums defined like
+    /// `Two = ONE + 1u8` work properly.
+    fn discriminant_consts(&self) -> TokenStream2 {
+        let repr = &self.repr;
+        let match_const_exprs = self.variants.iter().map(|variant| &variant.discriminant);
+        let enum_keys = self.variants.iter().map(|variant| &variant.ident);
+
+        quote! {
+            #(
+                const #enum_keys: #repr =
+                    #match_const_exprs
+                ;
+            )*
+        }
+    }
+
+    /// Generates a `match number { ... }` expression mapping each discriminant
+    /// (and alternative) to `wrap(variant)`, and anything else to `fallback`.
+    fn discriminant_match(
+        &self,
+        wrap: impl Fn(TokenStream2) -> TokenStream2,
+        fallback: TokenStream2,
+    ) -> TokenStream2 {
+        let Self { name, repr, .. } = self;
+        let discriminant_consts = self.discriminant_consts();
+
+        let (wide, check_overlaps) = if self.is_signed() {
+            (quote!(i128), quote!(check_overlaps_i128))
+        } else {
+            (quote!(u128), quote!(check_overlaps_u128))
+        };
+
+        let mut alternative_consts = Vec::new();
+        let mut overlap_entries = Vec::new();
+        let mut arms = Vec::with_capacity(self.variants.len());
+        for variant in &self.variants {
+            let ident = &variant.ident;
+            let ident_str = ident.to_string();
+            let mut patterns = vec![quote!(#ident)];
+            overlap_entries.push(quote! {
+                (#ident_str, #ident as #wide, #ident as #wide, false)
+            });
+            for (alternative, (start_ident, end_ident)) in variant
+                .alternatives
+                .iter()
+                .zip(variant.alternative_idents())
             {
-                enum_value as Self
+                let start = &alternative.start;
+                alternative_consts.push(quote!(const #start_ident: #repr = #start;));
+                let end_ident = match (&alternative.end, end_ident) {
+                    (Some(end), Some(end_ident)) => {
+                        alternative_consts.push(quote!(const #end_ident: #repr = #end;));
+                        patterns.push(quote!(#start_ident..=#end_ident));
+                        end_ident
+                    }
+                    _ => {
+                        patterns.push(quote!(#start_ident));
+                        start_ident.clone()
+                    }
+                };
+                overlap_entries.push(quote! {
+                    (#ident_str, #start_ident as #wide, #end_ident as #wide, true)
+                });
             }
+            let variant = wrap(variant.constructor(name));
+            arms.push(quote! {
+                #(| #patterns)* => #variant,
+            });
         }
-    })
-}
-
-/// Implements `TryFrom<Primitive>` for a `#[repr(Primitive)] enum`.
-///
-/// Attempting to turn a primitive into an enum with try_from.
-#[proc_macro_derive(TryFromPrimitive)]
-pub fn derive_try_from_primitive(input: TokenStream) -> TokenStream {
-    let EnumInfo {
-        name,
-        repr,
-        value_expressions_to_enum_keys,
-    } = parse_macro_input!(input);
 
-    let (match_const_exprs, enum_keys): (Vec<Expr>, Vec<Ident>) =
-        value_expressions_to_enum_keys.into_iter().unzip();
+        // Overlaps between discriminants are already reported by rustc, so
+        // only check when alternatives are involved. This happens in a const
+        // context so that it works for arbitrary expressions.
+        let overlap_check = if alternative_consts.is_empty() {
+            quote!()
+        } else {
+            let krate = &self.krate;
+            quote! {
+                const _: () = if let ::core::option::Option::Some(message) =
+                    #krate::__private::#check_overlaps(&[
+                        #(#overlap_entries,)*
+                    ])
+                {
+                    ::core::panic!("{}", message.as_str());
+                };
+            }
+        };
 
-    TokenStream::from(quote! {
-        impl ::num_enum::TryFromPrimitive for #name {
-            type Primitive = #repr;
+        // For enums whose discriminants form a contiguous range, a bounds
+        // check followed by a transmute beats matching on every value. Whether
+        // that's the case is only known once the consts are evaluated, but the
+        // branch not taken is optimized out.
+        let dense_lookup = if alternative_consts.is_empty() && self.can_transmute() {
+            let enum_keys = self.variants.iter().map(|variant| &variant.ident);
+            let min = const_extremum(quote!(&[#(#enum_keys),*]), quote!(<));
+            let enum_keys = self.variants.iter().map(|variant| &variant.ident);
+            let max = const_extremum(quote!(&[#(#enum_keys),*]), quote!(>));
+            let last_offset = self.variants.len() as u128 - 1;
+            let variant = wrap(quote! {
+                // SAFETY: `number` is in `MIN..=MAX`, all of which are
+                // discriminants of this fieldless enum.
+                unsafe { ::core::mem::transmute::<#repr, #name>(number) }
+            });
+            quote! {
+                const __NUM_ENUM_MIN: #repr = #min;
+                const __NUM_ENUM_MAX: #repr = #max;
+                // Discriminants are unique, so they're contiguous iff they
+                // span exactly as many values as there are variants.
+                const __NUM_ENUM_DENSE: bool =
+                    (__NUM_ENUM_MAX as #wide).wrapping_sub(__NUM_ENUM_MIN as #wide) as u128
+                        == #last_offset;
+                if __NUM_ENUM_DENSE {
+                    return if __NUM_ENUM_MIN <= number && number <= __NUM_ENUM_MAX {
+                        #variant
+                    } else {
+                        #fallback
+                    };
+                }
+            }
+        } else {
+            quote!()
+        };
 
-            fn try_from_primitive (
-                number: Self::Primitive,
-            ) -> ::core::result::Result<
-                Self, ()
-            >
+        quote! {
+            #[allow(non_upper_case_globals, clippy::manual_range_contains)]
             {
-                // Use intermediate const(s) so that enums defined like
-                // `Two = ONE + 1u8` work properly.
-                #![allow(non_upper_case_globals)]
-                #(
-                    const #enum_keys: #repr =
-                        #match_const_exprs
-                    ;
-                )*
+                #discriminant_consts
+                #(#alternative_consts)*
+                #overlap_check
+                #dense_lookup
                 match number {
+                    #(#arms)*
+                    | _ => #fallback,
+                }
+            }
+        }
+    }
+
+    /// Generates a `match #value { ... }` expression evaluating to the
+    /// primitive value of `value`, which may be a place behind a reference.
+    fn primitive_match(&self, value: TokenStream2) -> TokenStream2 {
+        let name = &self.name;
+        let discriminant_consts = self.discriminant_consts();
+        let patterns = self.variants.iter().map(|variant| variant.pattern(name));
+        let enum_keys = self.variants.iter().map(|variant| &variant.ident);
+        let catch_all_arm = self.catch_all.iter().map(|catch_all| {
+            quote! {
+                #name::#catch_all(raw) => raw,
+            }
+        });
+        let wildcard_arm = self.wildcard_arm();
+        quote! {
+            #[allow(non_upper_case_globals)]
+            {
+                #discriminant_consts
+                match #value {
                     #(
-                        | #enum_keys => ::core::result::Result::Ok(
-                            #name::#enum_keys
-                        ),
+                        #patterns => #enum_keys,
                     )*
-                    | _ => ::core::result::Result::Err(()),
+                    #(#catch_all_arm)*
+                    #wildcard_arm
                 }
             }
         }
+    }
 
-        impl ::core::convert::TryFrom<#repr> for #name {
-            type Error = ();
-
-            #[inline]
-            fn try_from (
-                number: #repr,
-            ) -> ::core::result::Result<Self, ()>
-            {
-                ::num_enum::TryFromPrimitive::try_from_primitive(number)
+    /// A wildcard arm for matches on the enum, when some of its variants were
+    /// left out of `variants`.
+    fn wildcard_arm(&self) -> TokenStream2 {
+        if self.incomplete {
+            quote! {
+                #[allow(unreachable_patterns)]
+                _ => ::core::unreachable!(),
             }
+        } else {
+            quote!()
         }
-    })
+    }
+
+    /// The value unknown primitives are mapped to, if the enum has a
+    /// `default` or `catch_all` variant.
+    fn fallback_variant(&self) -> Option<TokenStream2> {
+        let name = &self.name;
+        match (&self.default, &self.catch_all) {
+            (Some(default), _) => Some(self.variants[*default].constructor(name)),
+            (None, Some(catch_all)) => Some(quote!(#name::#catch_all(number))),
+            (None, None) => None,
+        }
+    }
 }
 
-/// Generates a `unsafe fn from_unchecked (number: Primitive) -> Self`
-/// associated function.
+/// Implements `Into<Primitive>` for a `#[repr(Primitive)] enum`.
 ///
-/// Allows unsafely turning a primitive into an enum.
-/// Creating enum with invalid discriminants is undefined behavior.
-#[proc_macro_derive(UnsafeFromPrimitive)]
-pub fn derive_unsafe_from_primitive(stream: TokenStream) -> TokenStream {
-    let EnumInfo { name, repr, .. } = parse_macro_input!(stream as EnumInfo);
+/// (It actually implements `From<Enum> for Primitive`, as well as
+/// `::num_enum::IntoPrimitive` and a `const fn to_primitive`)
+///
+/// ## Allows turning an enum into a primitive.
+///
+/// A `#[num_enum(catch_all)]` variant is turned into the value it holds.
+#[proc_macro_derive(IntoPrimitive, attributes(num_enum))]
+pub fn derive_into_primitive(input: TokenStream) -> TokenStream {
+    let mut enum_info = parse_macro_input!(input as EnumInfo);
+    let errors = ::core::mem::take(&mut enum_info.errors);
+    let EnumInfo {
+        name,
+        generics,
+        repr,
+        try_from,
+        variants,
+        catch_all,
+        krate,
+        ..
+    } = &enum_info;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let doc_string = LitStr::new(
+    let widenings = try_from
+        .iter()
+        .filter(|ty| is_lossless(&repr.to_string(), &ty.to_string()));
+
+    let body = if catch_all.is_some()
+        || enum_info.incomplete
+        || !variants.iter().all(VariantInfo::is_unit)
+    {
+        // Enums with fields can't be cast with `as`.
+        enum_info.primitive_match(quote!(self))
+    } else {
+        quote! {
+            self as #repr
+        }
+    };
+
+    TokenStream::from(quote! {
+        #errors
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Turns `self` into its primitive value, in `const` contexts.
+            #[inline]
+            pub const fn to_primitive (self) -> #repr
+            {
+                #body
+            }
+        }
+
+        impl #impl_generics #krate::IntoPrimitive for #name #ty_generics #where_clause {
+            type Primitive = #repr;
+
+            #[inline]
+            fn into_primitive (self) -> Self::Primitive
+            {
+                Self::to_primitive(self)
+            }
+        }
+
+        impl #impl_generics From<#name #ty_generics> for #repr #where_clause {
+            #[inline]
+            fn from (enum_value: #name #ty_generics) -> Self
+            {
+                #krate::IntoPrimitive::into_primitive(enum_value)
+            }
+        }
+
+        #(
+            impl #impl_generics From<#name #ty_generics> for #widenings #where_clause {
+                #[inline]
+                fn from (enum_value: #name #ty_generics) -> Self
+                {
+                    <#widenings as ::core::convert::From<#repr>>::from(
+                        #krate::IntoPrimitive::into_primitive(enum_value),
+                    )
+                }
+            }
+        )*
+    })
+}
+
+/// Implements `From<Primitive>` for a `#[repr(Primitive)] enum`.
+///
+/// Turning a primitive into an enum with from. Values that don't match any
+/// discriminant are mapped to the variant marked `#[num_enum(default)]`, or
+/// stored in the variant marked `#[num_enum(catch_all)]`. The types listed in
+/// `#[num_enum(try_from = [...])]` get a `TryFrom` impl which only fails on
+/// values out of range of the `repr`.
+///
+/// Can't be combined with `#[derive(TryFromPrimitive)]`, whose `TryFrom` impl
+/// would conflict with the `From` impl. `::num_enum::TryFromPrimitive` is
+/// implemented for every `::num_enum::FromPrimitive` type instead.
+#[proc_macro_derive(FromPrimitive, attributes(num_enum))]
+pub fn derive_from_primitive(input: TokenStream) -> TokenStream {
+    let mut enum_info = parse_macro_input!(input as EnumInfo);
+    let mut errors = ::core::mem::take(&mut enum_info.errors);
+    let EnumInfo {
+        name,
+        generics,
+        repr,
+        try_from,
+        krate,
+        ..
+    } = &enum_info;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fallback = match enum_info.fallback_variant() {
+        Some(fallback) => fallback,
+        None => {
+            if !enum_info.rejected_fallback {
+                errors.push_new(
+                    name.span(),
+                    "#[derive(FromPrimitive)] requires a variant marked `#[num_enum(default)]` or `#[num_enum(catch_all)]`",
+                );
+            }
+            placeholder()
+        }
+    };
+
+    let discriminant_match = enum_info.discriminant_match(|variant| variant, fallback);
+
+    TokenStream::from(quote! {
+        #errors
+
+        impl #impl_generics #krate::FromPrimitive for #name #ty_generics #where_clause {
+            type Primitive = #repr;
+
+            const NAME: &'static str = stringify!(#name);
+
+            fn from_primitive (
+                number: Self::Primitive,
+            ) -> Self
+            {
+                #discriminant_match
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#repr> for #name #ty_generics #where_clause {
+            #[inline]
+            fn from (
+                number: #repr,
+            ) -> Self
+            {
+                #krate::FromPrimitive::from_primitive(number)
+            }
+        }
+
+        #(
+            impl #impl_generics ::core::convert::TryFrom<#try_from> for #name #ty_generics #where_clause {
+                type Error = #krate::TryFromPrimitiveError<Self, #try_from>;
+
+                #[inline]
+                fn try_from (
+                    number: #try_from,
+                ) -> ::core::result::Result<Self, #krate::TryFromPrimitiveError<Self, #try_from>>
+                {
+                    <#repr as ::core::convert::TryFrom<#try_from>>::try_from(number)
+                        .map(#krate::FromPrimitive::from_primitive)
+                        .map_err(|_| #krate::TryFromPrimitiveError::new(number))
+                }
+            }
+        )*
+    })
+}
+
+/// Implements `TryFrom<Primitive>` for a `#[repr(Primitive)] enum`, as well as
+/// `::num_enum::TryFromPrimitive` and a `const fn try_from_primitive_const`.
+///
+/// Attempting to turn a primitive into an enum with try_from.
+/// If a variant is marked `#[num_enum(default)]` or `#[num_enum(catch_all)]`,
+/// unknown values map to it.
+#[proc_macro_derive(TryFromPrimitive, attributes(num_enum))]
+pub fn derive_try_from_primitive(input: TokenStream) -> TokenStream {
+    let mut enum_info = parse_macro_input!(input as EnumInfo);
+    let errors = ::core::mem::take(&mut enum_info.errors);
+    let EnumInfo {
+        name,
+        generics,
+        repr,
+        try_from,
+        krate,
+        ..
+    } = &enum_info;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let discriminant_match = enum_info.discriminant_match(
+        |variant| quote!(::core::option::Option::Some(#variant)),
+        match enum_info.fallback_variant() {
+            Some(fallback) => quote!(::core::option::Option::Some(#fallback)),
+            None => quote!(::core::option::Option::None),
+        },
+    );
+
+    TokenStream::from(quote! {
+        #errors
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Turns `number` into the matching variant, if any, in `const`
+            /// contexts.
+            pub const fn try_from_primitive_const (
+                number: #repr,
+            ) -> ::core::option::Option<Self>
+            {
+                #discriminant_match
+            }
+        }
+
+        impl #impl_generics #krate::TryFromPrimitive for #name #ty_generics #where_clause {
+            type Primitive = #repr;
+
+            const NAME: &'static str = stringify!(#name);
+
+            fn try_from_primitive (
+                number: Self::Primitive,
+            ) -> ::core::result::Result<
+                Self,
+                #krate::TryFromPrimitiveError<Self>,
+            >
+            {
+                match Self::try_from_primitive_const(number) {
+                    ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                    ::core::option::Option::None => ::core::result::Result::Err(
+                        #krate::TryFromPrimitiveError::new(number),
+                    ),
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<#repr> for #name #ty_generics #where_clause {
+            type Error = #krate::TryFromPrimitiveError<Self>;
+
+            #[inline]
+            fn try_from (
+                number: #repr,
+            ) -> ::core::result::Result<Self, #krate::TryFromPrimitiveError<Self>>
+            {
+                #krate::TryFromPrimitive::try_from_primitive(number)
+            }
+        }
+
+        #(
+            impl #impl_generics ::core::convert::TryFrom<#try_from> for #name #ty_generics #where_clause {
+                type Error = #krate::TryFromPrimitiveError<Self, #try_from>;
+
+                #[inline]
+                fn try_from (
+                    number: #try_from,
+                ) -> ::core::result::Result<Self, #krate::TryFromPrimitiveError<Self, #try_from>>
+                {
+                    <#repr as ::core::convert::TryFrom<#try_from>>::try_from(number)
+                        .ok()
+                        .and_then(Self::try_from_primitive_const)
+                        .ok_or(#krate::TryFromPrimitiveError::new(number))
+                }
+            }
+        )*
+    })
+}
+
+/// Implements `::num_enum::EnumMeta` for a `#[repr(Primitive)] enum`.
+///
+/// Lists the variants of an enum along with their discriminants, and converts
+/// them to and from their names. Names can be changed with
+/// `#[num_enum(rename = "...")]` on a variant, or
+/// `#[num_enum(rename_all = "...")]` on the enum.
+#[proc_macro_derive(EnumMeta, attributes(num_enum))]
+pub fn derive_enum_meta(input: TokenStream) -> TokenStream {
+    let mut enum_info = parse_macro_input!(input as EnumInfo);
+    let mut errors = ::core::mem::take(&mut enum_info.errors);
+    if let Some(catch_all) = &enum_info.catch_all {
+        errors.push_new(
+            catch_all.span(),
+            "#[derive(EnumMeta)] can't be used on enums with a `#[num_enum(catch_all)]` variant",
+        );
+        // Matches on the enum still need to cover it.
+        enum_info.incomplete = true;
+    }
+    let EnumInfo {
+        name,
+        generics,
+        repr,
+        variants,
+        krate,
+        ..
+    } = &enum_info;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // `EnumMeta` has a `'static` supertrait.
+    let mut meta_generics = generics.clone();
+    meta_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#name #ty_generics: 'static));
+    let (meta_impl_generics, meta_ty_generics, meta_where_clause) = meta_generics.split_for_impl();
+
+    let wildcard_arm = enum_info.wildcard_arm();
+
+    let discriminant_consts = enum_info.discriminant_consts();
+    let constructors = variants.iter().map(|variant| variant.constructor(name));
+    let constructors2 = constructors.clone();
+    let constructors3 = constructors.clone();
+    let enum_keys = variants.iter().map(|variant| &variant.ident);
+    let patterns = variants.iter().map(|variant| variant.pattern(name));
+    let patterns2 = patterns.clone();
+    let names = variants.iter().map(|variant| &variant.name);
+    let names2 = names.clone();
+    let count = variants.len();
+    let min = const_extremum(quote!(<Self as #krate::EnumMeta>::DISCRIMINANTS), quote!(<));
+    let max = const_extremum(quote!(<Self as #krate::EnumMeta>::DISCRIMINANTS), quote!(>));
+
+    // The indices (in declaration order) of the variants sorted by
+    // discriminant, and the position of each variant in that order.
+    let enum_keys2 = enum_keys.clone();
+    let sorted = quote! {
+        #[allow(non_upper_case_globals)]
+        const ORDER: [usize; #count] = {
+            #discriminant_consts
+            let discriminants: [#repr; #count] = [#(#enum_keys2,)*];
+            let mut order = [0; #count];
+            let mut i = 0;
+            while i < #count {
+                order[i] = i;
+                i += 1;
+            }
+            // Insertion sort, as `sort` isn't usable in const contexts.
+            let mut i = 1;
+            while i < #count {
+                let mut j = i;
+                while j > 0 && discriminants[order[j - 1]] > discriminants[order[j]] {
+                    let swapped = order[j - 1];
+                    order[j - 1] = order[j];
+                    order[j] = swapped;
+                    j -= 1;
+                }
+                i += 1;
+            }
+            order
+        };
+        const POSITIONS: [usize; #count] = {
+            let mut positions = [0; #count];
+            let mut i = 0;
+            while i < #count {
+                positions[ORDER[i]] = i;
+                i += 1;
+            }
+            positions
+        };
+    };
+    let indices = (0..count).collect::<Vec<_>>();
+    let variant_at = quote! {
+        |index: usize| match index {
+            #(#indices => #constructors3,)*
+            _ => ::core::unreachable!(),
+        }
+    };
+    let position = quote! {
+        POSITIONS[match self {
+            #(#patterns2 => #indices,)*
+            #wildcard_arm
+        }]
+    };
+
+    TokenStream::from(quote! {
+        #errors
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Iterates over every variant, sorted by discriminant.
+            pub fn iter() -> impl ::core::iter::DoubleEndedIterator<Item = Self>
+                + ::core::iter::ExactSizeIterator
+                + ::core::iter::FusedIterator
+                + ::core::clone::Clone
+            {
+                #sorted
+                ORDER.iter().map(|&index| (#variant_at)(index))
+            }
+
+            /// The variant with the next larger discriminant, if any.
+            pub fn next(&self) -> ::core::option::Option<Self> {
+                #sorted
+                ORDER.get(#position + 1).map(|&index| (#variant_at)(index))
+            }
+
+            /// The variant with the next smaller discriminant, if any.
+            pub fn prev(&self) -> ::core::option::Option<Self> {
+                #sorted
+                let position = #position;
+                if position == 0 {
+                    return ::core::option::Option::None;
+                }
+                ::core::option::Option::Some((#variant_at)(ORDER[position - 1]))
+            }
+
+            /// The variant with the next larger discriminant, wrapping around
+            /// to the smallest one.
+            pub fn next_wrapping(&self) -> Self {
+                #sorted
+                let position = #position;
+                if position == #count - 1 {
+                    (#variant_at)(ORDER[0])
+                } else {
+                    (#variant_at)(ORDER[position + 1])
+                }
+            }
+
+            /// The variant with the next smaller discriminant, wrapping around
+            /// to the largest one.
+            pub fn prev_wrapping(&self) -> Self {
+                #sorted
+                let position = #position;
+                if position == 0 {
+                    (#variant_at)(ORDER[#count - 1])
+                } else {
+                    (#variant_at)(ORDER[position - 1])
+                }
+            }
+        }
+
+        impl #meta_impl_generics #krate::EnumMeta for #name #meta_ty_generics #meta_where_clause {
+            type Primitive = #repr;
+
+            const VARIANTS: &'static [Self] = &[
+                #(#constructors,)*
+            ];
+
+            #[allow(non_upper_case_globals)]
+            const DISCRIMINANTS: &'static [Self::Primitive] = {
+                #discriminant_consts
+                &[#(#enum_keys,)*]
+            };
+
+            const COUNT: usize = #count;
+
+            const MIN: Self::Primitive = #min;
+
+            const MAX: Self::Primitive = #max;
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #(#patterns => #names,)*
+                    #wildcard_arm
+                }
+            }
+
+            fn from_name(name: &str) -> ::core::option::Option<Self> {
+                match name {
+                    #(#names2 => ::core::option::Option::Some(#constructors2),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+/// Implements `UnsafeFromPrimitive` for a `#[repr(Primitive)] enum`, and
+/// generates a `unsafe fn from_unchecked (number: Primitive) -> Self`
+/// associated function.
+///
+/// Allows unsafely turning a primitive into an enum.
+/// Creating enum with invalid discriminants is undefined behavior.
+///
+/// When `debug_assertions` are enabled, invalid discriminants panic instead.
+#[proc_macro_derive(UnsafeFromPrimitive, attributes(num_enum))]
+pub fn derive_unsafe_from_primitive(stream: TokenStream) -> TokenStream {
+    let mut enum_info = parse_macro_input!(stream as EnumInfo);
+    let mut errors = ::core::mem::take(&mut enum_info.errors);
+    let EnumInfo {
+        name,
+        generics,
+        repr,
+        variants,
+        catch_all,
+        krate,
+        ..
+    } = &enum_info;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if let Some(catch_all) = catch_all {
+        errors.push_new(
+            catch_all.span(),
+            "#[derive(UnsafeFromPrimitive)] can't be used on enums with a `#[num_enum(catch_all)]` variant",
+        );
+    }
+
+    let doc_string = LitStr::new(
         &format!(
-            r#"1
+            r#"
 Transmutes `number: {repr}` into a [`{name}`].
 
 # Safety
 
   - `number` must represent a valid discriminant of [`{name}`]
+
+# Panics
+
+When `debug_assertions` are enabled, if `number` isn't a valid discriminant.
 "#,
             repr = repr,
             name = name,
@@ -210,15 +1652,474 @@ Transmutes `number: {repr}` into a [`{name}`].
         Span::call_site(),
     );
 
+    // Alternatives are deliberately not accepted: only discriminants can be
+    // transmuted.
+    let discriminant_consts = enum_info.discriminant_consts();
+    let enum_keys = variants.iter().map(|variant| &variant.ident);
+    let error_message = format!("`{{:?}}` is not a valid discriminant of `{}`", name);
+
+    let conversion = if enum_info.can_transmute() {
+        quote! {
+            ::core::mem::transmute(number)
+        }
+    } else {
+        // Generic enums, and enums with `PhantomData` fields, can't be
+        // transmuted into.
+        let enum_keys = variants.iter().map(|variant| &variant.ident);
+        let constructors = variants.iter().map(|variant| variant.constructor(name));
+        quote! {
+            #[allow(non_upper_case_globals)]
+            {
+                #discriminant_consts
+                match number {
+                    #(#enum_keys => #constructors,)*
+                    _ => ::core::hint::unreachable_unchecked(),
+                }
+            }
+        }
+    };
+
     TokenStream::from(quote! {
-        impl #name {
+        #errors
+
+        impl #impl_generics #krate::UnsafeFromPrimitive for #name #ty_generics #where_clause {
+            type Primitive = #repr;
+
+            #[inline]
+            unsafe fn from_unchecked(number: Self::Primitive) -> Self {
+                #[cfg(debug_assertions)]
+                #[allow(non_upper_case_globals)]
+                {
+                    #discriminant_consts
+                    ::core::assert!(
+                        ::core::matches!(number, #(#enum_keys)|*),
+                        #error_message,
+                        number,
+                    );
+                }
+                #conversion
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
             #[doc = #doc_string]
             #[inline]
             pub
             unsafe
             fn from_unchecked(number: #repr) -> Self {
-                ::core::mem::transmute(number)
+                <Self as #krate::UnsafeFromPrimitive>::from_unchecked(number)
+            }
+        }
+    })
+}
+
+/// Implements `serde::Serialize` for a `#[repr(Primitive)] enum`, serializing
+/// it as its primitive value.
+///
+/// Requires the `serde` feature of `num_enum`.
+#[proc_macro_derive(SerializePrimitive, attributes(num_enum))]
+pub fn derive_serialize_primitive(input: TokenStream) -> TokenStream {
+    let mut enum_info = parse_macro_input!(input as EnumInfo);
+    let errors = ::core::mem::take(&mut enum_info.errors);
+    let EnumInfo {
+        name,
+        generics,
+        repr,
+        krate,
+        ..
+    } = &enum_info;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // `self` is borrowed, so it can't be turned into a primitive with
+    // `IntoPrimitive`, but the mapping is the same.
+    let primitive_match = enum_info.primitive_match(quote!(*self));
+
+    TokenStream::from(quote! {
+        #errors
+
+        impl #impl_generics #krate::__private::serde::Serialize for #name #ty_generics #where_clause {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: #krate::__private::serde::Serializer,
+            {
+                let number: #repr = { #primitive_match };
+                #krate::__private::serde::Serialize::serialize(&number, serializer)
+            }
+        }
+    })
+}
+
+/// Implements `serde::Deserialize` for a `#[repr(Primitive)] enum` which also
+/// derives `TryFromPrimitive`, deserializing it from its primitive value.
+///
+/// Values which don't match any variant produce an error mentioning them.
+///
+/// Requires the `serde` feature of `num_enum`.
+#[proc_macro_derive(DeserializePrimitive, attributes(num_enum))]
+pub fn derive_deserialize_primitive(input: TokenStream) -> TokenStream {
+    let mut enum_info = parse_macro_input!(input as EnumInfo);
+    let errors = ::core::mem::take(&mut enum_info.errors);
+    let EnumInfo {
+        name,
+        generics,
+        repr,
+        krate,
+        ..
+    } = &enum_info;
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    let mut de_generics = generics.clone();
+    de_generics.params.insert(0, parse_quote!('de));
+    let (impl_generics, _, _) = de_generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        #errors
+
+        impl #impl_generics #krate::__private::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: #krate::__private::serde::Deserializer<'de>,
+            {
+                let number = <#repr as #krate::__private::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                <Self as #krate::TryFromPrimitive>::try_from_primitive(number)
+                    .map_err(<D::Error as #krate::__private::serde::de::Error>::custom)
+            }
+        }
+    })
+}
+
+/// Implements `::num_enum::BitFlags` for a `#[repr(Primitive)] enum` whose
+/// discriminants are single bits, and generates a `{Enum}Set` type holding
+/// any combination of them, along with its `{Enum}SetIter` iterator.
+///
+/// Discriminants which aren't a single bit are rejected at compile time.
+#[proc_macro_derive(BitFlags, attributes(num_enum))]
+pub fn derive_bit_flags(input: TokenStream) -> TokenStream {
+    let mut enum_info = parse_macro_input!(input as EnumInfo);
+    let mut errors = ::core::mem::take(&mut enum_info.errors);
+    if let Some(catch_all) = &enum_info.catch_all {
+        errors.push_new(
+            catch_all.span(),
+            "#[derive(BitFlags)] can't be used on enums with a `#[num_enum(catch_all)]` variant",
+        );
+        // Matches on the enum still need to cover it.
+        enum_info.incomplete = true;
+    }
+    let EnumInfo {
+        vis,
+        name,
+        generics,
+        repr,
+        variants,
+        krate,
+        ..
+    } = &enum_info;
+
+    let wildcard_arm = enum_info.wildcard_arm();
+    if let Some(variant) = variants
+        .iter()
+        .find(|variant| !variant.alternatives.is_empty())
+    {
+        errors.push_new(
+            variant.ident.span(),
+            "#[derive(BitFlags)] can't be used on enums with alternatives",
+        );
+    }
+    if !generics.params.is_empty() {
+        // The set type would need the same generics, so there's no
+        // reasonable impl to fall back to.
+        errors.push_new(
+            generics.span(),
+            "#[derive(BitFlags)] can't be used on generic enums",
+        );
+        return errors.into_token_stream().into();
+    }
+
+    let set = format_ident!("{}Set", name);
+    let iter = format_ident!("{}SetIter", name);
+    let set_doc = LitStr::new(&format!("A set of [`{}`] flags.", name), Span::call_site());
+    let iter_doc = LitStr::new(
+        &format!("An iterator over the flags of a [`{}`].", set),
+        Span::call_site(),
+    );
+
+    let discriminant_consts = enum_info.discriminant_consts();
+    let single_bit_checks = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let message = format!("`{}` must be a single bit", ident);
+        quote_spanned! {ident.span()=>
+            if #ident.count_ones() != 1 {
+                ::core::panic!(#message);
+            }
+        }
+    });
+    let enum_keys = variants.iter().map(|variant| &variant.ident);
+    let enum_keys2 = enum_keys.clone();
+    let constructors = variants.iter().map(|variant| variant.constructor(name));
+    let patterns = variants.iter().map(|variant| variant.pattern(name));
+    let idents = variants.iter().map(|variant| variant.ident.to_string());
+    let bit = enum_info.primitive_match(quote!(flag));
+
+    TokenStream::from(quote! {
+        #errors
+
+        #[allow(non_upper_case_globals)]
+        const _: () = {
+            #discriminant_consts
+            #(#single_bit_checks)*
+        };
+
+        impl #krate::BitFlags for #name {
+            type Primitive = #repr;
+
+            type Set = #set;
+
+            const NAME: &'static str = stringify!(#name);
+
+            #[allow(non_upper_case_globals)]
+            const ALL: #repr = {
+                #discriminant_consts
+                0 #(| #enum_keys)*
+            };
+        }
+
+        #[doc = #set_doc]
+        #[derive(
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+            ::core::default::Default,
+        )]
+        #vis struct #set {
+            bits: #repr,
+        }
+
+        impl #set {
+            /// The set containing no flags.
+            #[inline]
+            pub const fn empty() -> Self {
+                Self { bits: 0 }
+            }
+
+            /// The set containing every flag.
+            #[inline]
+            pub const fn all() -> Self {
+                Self {
+                    bits: <#name as #krate::BitFlags>::ALL,
+                }
+            }
+
+            /// The bits of the flags in this set.
+            #[inline]
+            pub const fn bits(self) -> #repr {
+                self.bits
+            }
+
+            /// The set of flags with the given bits, unless some of them don't
+            /// match any flag.
+            #[inline]
+            pub const fn from_bits(bits: #repr) -> ::core::option::Option<Self> {
+                if bits & !<#name as #krate::BitFlags>::ALL == 0 {
+                    ::core::option::Option::Some(Self { bits })
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            /// The set of flags with the given bits, dropping those which don't
+            /// match any flag.
+            #[inline]
+            pub const fn from_bits_truncate(bits: #repr) -> Self {
+                Self {
+                    bits: bits & <#name as #krate::BitFlags>::ALL,
+                }
+            }
+
+            /// Whether this set contains no flags.
+            #[inline]
+            pub const fn is_empty(self) -> bool {
+                self.bits == 0
+            }
+
+            /// The number of flags in this set.
+            #[inline]
+            pub const fn len(self) -> usize {
+                self.bits.count_ones() as usize
+            }
+
+            /// Whether this set contains every flag of `other`.
+            #[inline]
+            pub fn contains(self, other: impl ::core::convert::Into<Self>) -> bool {
+                let other = other.into();
+                self.bits & other.bits == other.bits
+            }
+
+            /// Adds the flags of `other` to this set.
+            #[inline]
+            pub fn insert(&mut self, other: impl ::core::convert::Into<Self>) {
+                self.bits |= other.into().bits;
+            }
+
+            /// Removes the flags of `other` from this set.
+            #[inline]
+            pub fn remove(&mut self, other: impl ::core::convert::Into<Self>) {
+                self.bits &= !other.into().bits;
+            }
+
+            /// Iterates over the flags in this set, from the lowest bit up.
+            #[inline]
+            pub const fn iter(self) -> #iter {
+                #iter { remaining: self.bits }
+            }
+        }
+
+        impl ::core::fmt::Debug for #set {
+            fn fmt(&self, stream: &'_ mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                stream.write_str(::core::concat!(::core::stringify!(#set), "("))?;
+                for (i, flag) in self.iter().enumerate() {
+                    if i > 0 {
+                        stream.write_str(" | ")?;
+                    }
+                    stream.write_str(match flag {
+                        #(#patterns => #idents,)*
+                        #wildcard_arm
+                    })?;
+                }
+                stream.write_str(")")
+            }
+        }
+
+        impl ::core::convert::From<#name> for #set {
+            #[inline]
+            fn from(flag: #name) -> Self {
+                Self { bits: { #bit } }
+            }
+        }
+
+        impl ::core::convert::From<#set> for #repr {
+            #[inline]
+            fn from(set: #set) -> Self {
+                set.bits
+            }
+        }
+
+        impl ::core::convert::TryFrom<#repr> for #set {
+            type Error = #krate::TryFromBitsError<#name>;
+
+            #[inline]
+            fn try_from(bits: #repr) -> ::core::result::Result<Self, Self::Error> {
+                Self::from_bits(bits).ok_or(#krate::TryFromBitsError { bits })
+            }
+        }
+
+        impl<T: ::core::convert::Into<#set>> ::core::ops::BitOr<T> for #set {
+            type Output = #set;
+
+            #[inline]
+            fn bitor(self, other: T) -> #set {
+                #set {
+                    bits: self.bits | other.into().bits,
+                }
+            }
+        }
+
+        impl<T: ::core::convert::Into<#set>> ::core::ops::BitOr<T> for #name {
+            type Output = #set;
+
+            #[inline]
+            fn bitor(self, other: T) -> #set {
+                #set::from(self) | other
+            }
+        }
+
+        impl<T: ::core::convert::Into<#set>> ::core::ops::BitOrAssign<T> for #set {
+            #[inline]
+            fn bitor_assign(&mut self, other: T) {
+                self.bits |= other.into().bits;
+            }
+        }
+
+        impl<T: ::core::convert::Into<#set>> ::core::ops::BitAnd<T> for #set {
+            type Output = #set;
+
+            #[inline]
+            fn bitand(self, other: T) -> #set {
+                #set {
+                    bits: self.bits & other.into().bits,
+                }
+            }
+        }
+
+        impl<T: ::core::convert::Into<#set>> ::core::ops::BitAnd<T> for #name {
+            type Output = #set;
+
+            #[inline]
+            fn bitand(self, other: T) -> #set {
+                #set::from(self) & other
             }
         }
+
+        impl<T: ::core::convert::Into<#set>> ::core::ops::BitAndAssign<T> for #set {
+            #[inline]
+            fn bitand_assign(&mut self, other: T) {
+                self.bits &= other.into().bits;
+            }
+        }
+
+        impl<T: ::core::convert::Into<#set>> ::core::iter::FromIterator<T> for #set {
+            fn from_iter<I: ::core::iter::IntoIterator<Item = T>>(flags: I) -> Self {
+                let mut set = Self::empty();
+                for flag in flags {
+                    set |= flag;
+                }
+                set
+            }
+        }
+
+        impl ::core::iter::IntoIterator for #set {
+            type Item = #name;
+
+            type IntoIter = #iter;
+
+            #[inline]
+            fn into_iter(self) -> #iter {
+                self.iter()
+            }
+        }
+
+        #[doc = #iter_doc]
+        #[derive(::core::clone::Clone, ::core::fmt::Debug)]
+        #vis struct #iter {
+            remaining: #repr,
+        }
+
+        impl ::core::iter::Iterator for #iter {
+            type Item = #name;
+
+            #[allow(non_upper_case_globals)]
+            fn next(&mut self) -> ::core::option::Option<#name> {
+                if self.remaining == 0 {
+                    return ::core::option::Option::None;
+                }
+                let lowest = self.remaining & self.remaining.wrapping_neg();
+                self.remaining &= !lowest;
+                #discriminant_consts
+                ::core::option::Option::Some(match lowest {
+                    #(#enum_keys2 => #constructors,)*
+                    _ => ::core::unreachable!(),
+                })
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                let len = self.remaining.count_ones() as usize;
+                (len, ::core::option::Option::Some(len))
+            }
+        }
+
+        impl ::core::iter::ExactSizeIterator for #iter {}
+
+        impl ::core::iter::FusedIterator for #iter {}
     })
 }